@@ -0,0 +1,208 @@
+// src/build_targets.rs
+use crate::manifest::Manifest;
+use clap::ValueEnum;
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TargetKind {
+    Lib,
+    Bin,
+    Example,
+    Test,
+    Bench,
+}
+
+impl TargetKind {
+    pub const ALL: [TargetKind; 5] = [
+        TargetKind::Lib,
+        TargetKind::Bin,
+        TargetKind::Example,
+        TargetKind::Test,
+        TargetKind::Bench,
+    ];
+
+    /// Manifest key: `[lib]` or `[[bin]]`, `[[example]]`, ...
+    pub fn section(self) -> &'static str {
+        match self {
+            TargetKind::Lib => "lib",
+            TargetKind::Bin => "bin",
+            TargetKind::Example => "example",
+            TargetKind::Test => "test",
+            TargetKind::Bench => "bench",
+        }
+    }
+
+    /// The `[package]` flag controlling auto-discovery for this kind.
+    pub fn auto_key(self) -> &'static str {
+        match self {
+            TargetKind::Lib => "autolib",
+            TargetKind::Bin => "autobins",
+            TargetKind::Example => "autoexamples",
+            TargetKind::Test => "autotests",
+            TargetKind::Bench => "autobenches",
+        }
+    }
+
+    /// Directory scanned for targets of this kind.
+    fn dir(self) -> Option<&'static str> {
+        match self {
+            TargetKind::Lib => None,
+            TargetKind::Bin => Some("src/bin"),
+            TargetKind::Example => Some("examples"),
+            TargetKind::Test => Some("tests"),
+            TargetKind::Bench => Some("benches"),
+        }
+    }
+}
+
+pub struct BuildTarget {
+    pub kind: TargetKind,
+    pub name: String,
+    /// The path as written in the manifest or discovered on disk, relative to the
+    /// manifest directory. `None` when a declared target relies on the default.
+    pub path: Option<String>,
+}
+
+/// Targets declared in `[lib]`, `[[bin]]`, `[[example]]`, `[[test]]` and `[[bench]]`.
+pub fn explicit_targets(manifest: &Manifest) -> Vec<BuildTarget> {
+    let mut targets = Vec::new();
+    for kind in TargetKind::ALL {
+        for entry in target_entries(manifest, kind) {
+            let name = entry
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map(|s| s.to_string())
+                .or_else(|| default_target_name(manifest, kind))
+                .unwrap_or_default();
+            let path = entry
+                .get("path")
+                .and_then(|p| p.as_str())
+                .map(|s| s.to_string());
+            targets.push(BuildTarget { kind, name, path });
+        }
+    }
+    targets
+}
+
+/// Targets cargo would infer from the standard layout, regardless of `auto*` flags.
+pub fn discovered_targets(manifest: &Manifest) -> Vec<BuildTarget> {
+    let root = manifest.path.parent().unwrap_or(Path::new("."));
+    let package_name = manifest.get_package_name().unwrap_or_default();
+    let mut targets = Vec::new();
+
+    if root.join("src/lib.rs").is_file() {
+        targets.push(BuildTarget {
+            kind: TargetKind::Lib,
+            name: package_name.replace('-', "_"),
+            path: Some("src/lib.rs".to_string()),
+        });
+    }
+    if root.join("src/main.rs").is_file() {
+        targets.push(BuildTarget {
+            kind: TargetKind::Bin,
+            name: package_name.clone(),
+            path: Some("src/main.rs".to_string()),
+        });
+    }
+
+    for kind in TargetKind::ALL {
+        let Some(dir) = kind.dir() else { continue };
+        let Ok(read_dir) = fs::read_dir(root.join(dir)) else {
+            continue;
+        };
+        let mut found = Vec::new();
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if path.is_file() {
+                if let Some(stem) = file_name.strip_suffix(".rs") {
+                    found.push((stem.to_string(), format!("{}/{}", dir, file_name)));
+                }
+            } else if path.join("main.rs").is_file() {
+                found.push((file_name.clone(), format!("{}/{}/main.rs", dir, file_name)));
+            }
+        }
+        found.sort();
+        for (name, path) in found {
+            targets.push(BuildTarget {
+                kind,
+                name,
+                path: Some(path),
+            });
+        }
+    }
+
+    targets
+}
+
+/// Whether cargo auto-discovers targets of `kind` for this package.
+pub fn auto_discovery_enabled(manifest: &Manifest, kind: TargetKind) -> bool {
+    manifest
+        .package()
+        .and_then(|p| p.get(kind.auto_key()))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// The paths cargo tries, in order, for an explicit target that does not set
+/// `path`: `<dir>/<name>.rs`, then `<dir>/<name>/main.rs`.
+pub fn default_target_paths(manifest: &Manifest, kind: TargetKind, name: &str) -> Vec<String> {
+    match kind {
+        TargetKind::Lib => vec!["src/lib.rs".to_string()],
+        TargetKind::Bin if manifest.get_package_name().as_deref() == Some(name) => {
+            vec!["src/main.rs".to_string()]
+        }
+        _ => {
+            let dir = kind.dir().unwrap_or("src");
+            vec![
+                format!("{}/{}.rs", dir, name),
+                format!("{}/{}/main.rs", dir, name),
+            ]
+        }
+    }
+}
+
+/// The path cargo uses for an explicit target that does not set `path`: the
+/// first of [`default_target_paths`] that exists, or the first one if none does.
+pub fn default_target_path(manifest: &Manifest, kind: TargetKind, name: &str) -> String {
+    let root = manifest.path.parent().unwrap_or(Path::new("."));
+    let mut candidates = default_target_paths(manifest, kind, name);
+    let found = candidates
+        .iter()
+        .position(|c| root.join(c).is_file())
+        .unwrap_or(0);
+    candidates.swap_remove(found)
+}
+
+fn default_target_name(manifest: &Manifest, kind: TargetKind) -> Option<String> {
+    match kind {
+        TargetKind::Lib => manifest.get_package_name().map(|n| n.replace('-', "_")),
+        _ => None,
+    }
+}
+
+/// The entries for `kind`, whether written as `[[bin]]` tables or as an inline
+/// `bin = [{ ... }]` array.
+pub fn target_entries(manifest: &Manifest, kind: TargetKind) -> Vec<&dyn toml_edit::TableLike> {
+    let Some(item) = manifest.doc.get(kind.section()) else {
+        return Vec::new();
+    };
+    if kind == TargetKind::Lib {
+        return item.as_table_like().into_iter().collect();
+    }
+    if let Some(tables) = item.as_array_of_tables() {
+        return tables
+            .iter()
+            .map(|t| t as &dyn toml_edit::TableLike)
+            .collect();
+    }
+    if let Some(array) = item.as_array() {
+        return array
+            .iter()
+            .filter_map(|v| v.as_inline_table())
+            .map(|t| t as &dyn toml_edit::TableLike)
+            .collect();
+    }
+    Vec::new()
+}
@@ -1,6 +1,6 @@
 // src/commands/categories.rs
 use crate::manifest::Manifest;
-use crate::utils::{print_info, print_success, print_warning};
+use crate::utils::{closest, print_info, print_success, print_warning};
 use crate::CategoriesCommands;
 use anyhow::{Context, Result};
 use once_cell::sync::Lazy;
@@ -81,6 +81,15 @@ static VALID_CATEGORIES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     ])
 });
 
+pub fn is_valid_category(slug: &str) -> bool {
+    VALID_CATEGORIES.contains(slug)
+}
+
+/// The closest valid category slug, if it is reasonably close.
+pub fn suggest_category(slug: &str) -> Option<&'static str> {
+    closest(slug, VALID_CATEGORIES.iter().copied())
+}
+
 pub fn handle(path: &Path, cmd: CategoriesCommands, dry_run: bool, quiet: bool) -> Result<()> {
     match cmd {
         CategoriesCommands::Add { categories } => add(path, categories, dry_run, quiet),
@@ -0,0 +1,194 @@
+// src/commands/changelog.rs
+use super::version::current_version;
+use crate::manifest::Manifest;
+use crate::semver::Version;
+use crate::utils::{print_info, print_success, print_warning, today};
+use crate::ChangelogCommands;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn handle(path: &Path, cmd: ChangelogCommands, dry_run: bool, quiet: bool) -> Result<()> {
+    let manifest = Manifest::load(path)?;
+
+    match cmd {
+        ChangelogCommands::Release { version } => {
+            let version = match version {
+                Some(v) => Version::parse(&v)
+                    .map_err(|e| anyhow::anyhow!("Invalid version '{}': {}", v, e))?,
+                None => current_version(&manifest)?,
+            };
+            let (changelog_path, content) = prepare_release(&manifest, &version, quiet)?;
+            if dry_run {
+                print_info("(dry run - no changes made)", false);
+                return Ok(());
+            }
+            fs::write(&changelog_path, content)
+                .with_context(|| format!("Failed to write {}", changelog_path.display()))?;
+            print_success(&format!("Released {} in the changelog", version), quiet);
+        }
+    }
+
+    Ok(())
+}
+
+/// The changelog with `[Unreleased]` released as `version`, and its path.
+pub fn prepare_release(
+    manifest: &Manifest,
+    version: &Version,
+    quiet: bool,
+) -> Result<(PathBuf, String)> {
+    let path = changelog_path(manifest);
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let repository = manifest
+        .package()
+        .and_then(|p| p.get("repository"))
+        .and_then(|r| r.as_str())
+        .map(|r| r.trim_end_matches('/').trim_end_matches(".git").to_string());
+    if repository.is_none() {
+        print_warning("No 'repository' field; compare links are not updated");
+    }
+
+    let date = today();
+    let updated = release_changelog(&content, manifest, version, &date, repository.as_deref())?;
+    print_info(
+        &format!(
+            "{}: [Unreleased] → [{}] - {}",
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            version,
+            date
+        ),
+        quiet,
+    );
+    Ok((path, updated))
+}
+
+/// `changelog` in `[package.metadata.cargoe]`, relative to the manifest.
+fn changelog_path(manifest: &Manifest) -> PathBuf {
+    let file = manifest
+        .cargoe_config()
+        .and_then(|c| c.get("changelog"))
+        .and_then(|c| c.as_str())
+        .unwrap_or("CHANGELOG.md");
+    manifest.path.parent().unwrap_or(Path::new(".")).join(file)
+}
+
+/// The git tag for `version`, from `tag-format` in `[package.metadata.cargoe]`
+/// (default `v{version}`; `{name}` is the package name).
+pub fn tag_name(manifest: &Manifest, version: &str) -> String {
+    let format = manifest
+        .cargoe_config()
+        .and_then(|c| c.get("tag-format"))
+        .and_then(|f| f.as_str())
+        .unwrap_or("v{version}");
+    format
+        .replace("{name}", &manifest.get_package_name().unwrap_or_default())
+        .replace("{version}", version)
+}
+
+fn release_changelog(
+    content: &str,
+    manifest: &Manifest,
+    version: &Version,
+    date: &str,
+    repository: Option<&str>,
+) -> Result<String> {
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let version = version.to_string();
+
+    let Some(unreleased) = lines.iter().position(|l| is_heading(l, "unreleased")) else {
+        bail!("No '## [Unreleased]' section found in the changelog");
+    };
+    if lines.iter().any(|l| is_heading(l, &version)) {
+        bail!("The changelog already has a section for {}", version);
+    }
+
+    let next_heading = lines[unreleased + 1..]
+        .iter()
+        .position(|l| l.starts_with("## "))
+        .map(|i| unreleased + 1 + i);
+    let section_end = next_heading.unwrap_or(lines.len());
+    if lines[unreleased + 1..section_end]
+        .iter()
+        .all(|l| l.trim().is_empty() || is_link_definition(l))
+    {
+        print_warning("The [Unreleased] section is empty");
+    }
+    let previous = next_heading.and_then(|i| heading_name(&lines[i]));
+
+    lines.splice(
+        unreleased..=unreleased,
+        [
+            "## [Unreleased]".to_string(),
+            String::new(),
+            format!("## [{}] - {}", version, date),
+        ],
+    );
+
+    if let Some(repository) = repository {
+        let tag = tag_name(manifest, &version);
+        let unreleased_link = format!("[Unreleased]: {}", compare_url(repository, &tag, "HEAD"));
+        let version_link = match &previous {
+            Some(previous) => format!(
+                "[{}]: {}",
+                version,
+                compare_url(repository, &tag_name(manifest, previous), &tag)
+            ),
+            None => format!("[{}]: {}", version, tag_url(repository, &tag)),
+        };
+
+        match lines
+            .iter()
+            .position(|l| l.to_lowercase().starts_with("[unreleased]:"))
+        {
+            Some(i) => {
+                lines.splice(i..=i, [unreleased_link, version_link]);
+            }
+            None => {
+                if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                    lines.push(String::new());
+                }
+                lines.push(unreleased_link);
+                lines.push(version_link);
+            }
+        }
+    }
+
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    Ok(updated)
+}
+
+fn is_heading(line: &str, name: &str) -> bool {
+    heading_name(line).is_some_and(|h| h.eq_ignore_ascii_case(name))
+}
+
+/// `1.2.0` for `## [1.2.0] - 2024-01-01`.
+fn heading_name(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("## [")?;
+    rest.split_once(']').map(|(name, _)| name.to_string())
+}
+
+fn is_link_definition(line: &str) -> bool {
+    line.starts_with('[') && line.contains("]: ")
+}
+
+fn compare_url(repository: &str, from: &str, to: &str) -> String {
+    if repository.contains("gitlab") {
+        format!("{}/-/compare/{}...{}", repository, from, to)
+    } else {
+        format!("{}/compare/{}...{}", repository, from, to)
+    }
+}
+
+fn tag_url(repository: &str, tag: &str) -> String {
+    if repository.contains("gitlab") {
+        format!("{}/-/tags/{}", repository, tag)
+    } else {
+        format!("{}/releases/tag/{}", repository, tag)
+    }
+}
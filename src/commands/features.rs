@@ -0,0 +1,446 @@
+// src/commands/features.rs
+use crate::feature_graph::{FeatureGraph, FeatureValue};
+use crate::manifest::Manifest;
+use crate::utils::{print_error, print_info, print_success};
+use crate::{FeaturesCommands, GraphFormat, MatrixFormat};
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+const DOC_START_MARKER: &str = "<!-- cargoe:features:start -->";
+const DOC_END_MARKER: &str = "<!-- cargoe:features:end -->";
+
+pub fn handle(path: &Path, cmd: FeaturesCommands, dry_run: bool, quiet: bool) -> Result<()> {
+    match cmd {
+        FeaturesCommands::Graph { format } => graph(path, format),
+        FeaturesCommands::Doc { check } => doc(path, check, dry_run, quiet),
+        FeaturesCommands::Matrix {
+            depth,
+            exclusive,
+            format,
+        } => matrix(path, depth, exclusive, format),
+    }
+}
+
+#[derive(PartialEq)]
+enum NodeKind {
+    Feature,
+    Dependency,
+}
+
+struct Node {
+    id: String,
+    label: String,
+    kind: NodeKind,
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+    weak: bool,
+}
+
+fn graph(path: &Path, format: GraphFormat) -> Result<()> {
+    let manifest = Manifest::load(path)?;
+    let graph = FeatureGraph::from_manifest(&manifest);
+
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut edges: Vec<Edge> = Vec::new();
+
+    for (name, _) in &graph.features {
+        node_index(&mut nodes, name, NodeKind::Feature);
+    }
+    for name in graph.implicit_features() {
+        let from = node_index(&mut nodes, &name, NodeKind::Feature);
+        let to = node_index(&mut nodes, &name, NodeKind::Dependency);
+        edges.push(Edge {
+            from,
+            to,
+            label: None,
+            weak: false,
+        });
+    }
+
+    for (name, entries) in &graph.features {
+        let from = node_index(&mut nodes, name, NodeKind::Feature);
+        for entry in entries {
+            let edge = match FeatureValue::parse(entry) {
+                FeatureValue::Feature(f) => Edge {
+                    from,
+                    to: node_index(&mut nodes, &f, NodeKind::Feature),
+                    label: None,
+                    weak: false,
+                },
+                FeatureValue::Dep(d) => Edge {
+                    from,
+                    to: node_index(&mut nodes, &d, NodeKind::Dependency),
+                    label: None,
+                    weak: false,
+                },
+                FeatureValue::DepFeature { dep, feature, weak } => Edge {
+                    from,
+                    to: node_index(&mut nodes, &dep, NodeKind::Dependency),
+                    label: Some(feature),
+                    weak,
+                },
+            };
+            edges.push(edge);
+        }
+    }
+
+    let output = match format {
+        GraphFormat::Dot => render_dot(&nodes, &edges),
+        GraphFormat::Mermaid => render_mermaid(&nodes, &edges),
+    };
+    print!("{}", output);
+
+    Ok(())
+}
+
+fn node_index(nodes: &mut Vec<Node>, name: &str, kind: NodeKind) -> usize {
+    if let Some(i) = nodes.iter().position(|n| n.label == name && n.kind == kind) {
+        return i;
+    }
+    let prefix = match kind {
+        NodeKind::Feature => "f",
+        NodeKind::Dependency => "d",
+    };
+    nodes.push(Node {
+        id: format!("{}{}", prefix, nodes.len()),
+        label: name.to_string(),
+        kind,
+    });
+    nodes.len() - 1
+}
+
+fn render_dot(nodes: &[Node], edges: &[Edge]) -> String {
+    let mut out = String::from("digraph features {\n    rankdir=LR;\n    node [shape=box];\n");
+
+    for node in nodes {
+        let mut attrs = vec![format!("label=\"{}\"", escape(&node.label))];
+        if node.kind == NodeKind::Dependency {
+            attrs.push("shape=ellipse".to_string());
+        }
+        if node.kind == NodeKind::Feature && node.label == "default" {
+            attrs.push("style=bold".to_string());
+        }
+        out.push_str(&format!("    {} [{}];\n", node.id, attrs.join(", ")));
+    }
+
+    for edge in edges {
+        let mut attrs = Vec::new();
+        if let Some(label) = &edge.label {
+            attrs.push(format!("label=\"{}\"", escape(label)));
+        }
+        if edge.weak {
+            attrs.push("style=dashed".to_string());
+        }
+        let attrs = if attrs.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attrs.join(", "))
+        };
+        out.push_str(&format!(
+            "    {} -> {}{};\n",
+            nodes[edge.from].id, nodes[edge.to].id, attrs
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn render_mermaid(nodes: &[Node], edges: &[Edge]) -> String {
+    let mut out = String::from("graph LR\n");
+
+    for node in nodes {
+        let label = escape(&node.label);
+        match node.kind {
+            NodeKind::Feature => out.push_str(&format!("    {}[\"{}\"]\n", node.id, label)),
+            NodeKind::Dependency => out.push_str(&format!("    {}([\"{}\"])\n", node.id, label)),
+        }
+    }
+
+    for edge in edges {
+        let arrow = if edge.weak { "-.->" } else { "-->" };
+        let label = edge
+            .label
+            .as_ref()
+            .map(|l| format!("|\"{}\"|", escape(l)))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "    {} {}{} {}\n",
+            nodes[edge.from].id, arrow, label, nodes[edge.to].id
+        ));
+    }
+
+    for node in nodes {
+        if node.kind == NodeKind::Feature && node.label == "default" {
+            out.push_str(&format!("    style {} stroke-width:3px\n", node.id));
+        }
+    }
+
+    out
+}
+
+fn escape(s: &str) -> String {
+    s.replace('"', "\\\"")
+}
+
+fn doc(path: &Path, check: bool, dry_run: bool, quiet: bool) -> Result<()> {
+    let manifest = Manifest::load(path)?;
+    let graph = FeatureGraph::from_manifest(&manifest);
+
+    let readme = manifest
+        .package()
+        .and_then(|p| p.get("readme"))
+        .and_then(|r| r.as_str())
+        .unwrap_or("README.md")
+        .to_string();
+    let readme_path = path.parent().unwrap_or(Path::new(".")).join(&readme);
+    let original = fs::read_to_string(&readme_path)
+        .with_context(|| format!("Failed to read {}", readme_path.display()))?;
+
+    let start = original.find(DOC_START_MARKER).with_context(|| {
+        format!(
+            "{} has no '{}' marker; add it together with '{}' where the table should go",
+            readme, DOC_START_MARKER, DOC_END_MARKER
+        )
+    })?;
+    let end = original[start..]
+        .find(DOC_END_MARKER)
+        .map(|i| start + i)
+        .with_context(|| format!("{} has no '{}' marker", readme, DOC_END_MARKER))?;
+
+    let defaults = graph.resolve(graph.get("default").map(|_| "default"));
+    let features_table = manifest.doc.get("features").and_then(|f| f.as_table());
+
+    let mut table = String::from("| Feature | Description | Enables | Default |\n");
+    table.push_str("|---------|-------------|---------|---------|\n");
+    for (name, entries) in &graph.features {
+        if name == "default" {
+            continue;
+        }
+        let description = features_table
+            .and_then(|t| feature_description(t, name))
+            .unwrap_or_default();
+        let enables = entries
+            .iter()
+            .map(|e| format!("`{}`", e))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let default = if defaults.contains(name) { "✓" } else { "" };
+        table.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            name,
+            description.replace('|', "\\|"),
+            enables,
+            default
+        ));
+    }
+
+    let updated = format!(
+        "{}{}\n{}{}",
+        &original[..start],
+        DOC_START_MARKER,
+        table,
+        &original[end..]
+    );
+
+    if check {
+        if updated == original {
+            print_success(
+                &format!("Feature documentation in {} is up to date", readme),
+                quiet,
+            );
+            return Ok(());
+        }
+        print_error(&format!(
+            "Feature documentation in {} is out of date",
+            readme
+        ));
+        println!("\nRun 'cargoe features doc' to update it");
+        anyhow::bail!("Feature documentation check failed");
+    }
+
+    print_info(table.trim_end(), quiet);
+    if updated == original {
+        print_info(&format!("{} is already up to date", readme), quiet);
+    } else if !dry_run {
+        fs::write(&readme_path, updated)
+            .with_context(|| format!("Failed to write {}", readme_path.display()))?;
+        print_success(
+            &format!("Feature documentation written to {}", readme),
+            quiet,
+        );
+    } else {
+        print_info("(dry run - no changes made)", false);
+    }
+
+    Ok(())
+}
+
+/// Reads the comment lines directly above a feature entry.
+fn feature_description(table: &toml_edit::Table, name: &str) -> Option<String> {
+    let prefix = table.key(name)?.leaf_decor().prefix()?.as_str()?;
+
+    // Only the comment block touching the entry belongs to it; the text after
+    // the last newline is the key's own indentation
+    let mut above = prefix.split('\n').rev();
+    above.next();
+    let mut lines = Vec::new();
+    for line in above {
+        match line.trim().strip_prefix('#') {
+            Some(comment) => lines.push(comment.trim_start_matches('#').trim()),
+            None => break,
+        }
+    }
+    lines.reverse();
+
+    let description = lines.join(" ").trim().to_string();
+    if description.is_empty() {
+        None
+    } else {
+        Some(description)
+    }
+}
+
+/// `features matrix` combines at most this many features unless `--depth` says otherwise.
+pub const DEFAULT_MATRIX_DEPTH: usize = 2;
+/// Each combination resolves the feature graph several times, so larger
+/// powersets take too long to enumerate.
+const MAX_COMBINATIONS: u128 = 100_000;
+/// GitHub Actions rejects matrices that expand to more jobs than this.
+const GITHUB_MATRIX_LIMIT: usize = 256;
+
+fn matrix(path: &Path, depth: usize, exclusive: Vec<String>, format: MatrixFormat) -> Result<()> {
+    let manifest = Manifest::load(path)?;
+    let graph = FeatureGraph::from_manifest(&manifest);
+
+    let mut groups: Vec<Vec<String>> = exclusive
+        .iter()
+        .map(|g| g.split(',').map(|f| f.trim().to_string()).collect())
+        .collect();
+    if let Some(configured) = manifest
+        .cargoe_config()
+        .and_then(|c| c.get("mutually-exclusive-features"))
+        .and_then(|g| g.as_array())
+    {
+        for group in configured.iter().filter_map(|g| g.as_array()) {
+            groups.push(
+                group
+                    .iter()
+                    .filter_map(|f| f.as_str().map(|s| s.to_string()))
+                    .collect(),
+            );
+        }
+    }
+    for feature in groups.iter().flatten() {
+        if graph.get(feature).is_none() {
+            anyhow::bail!(
+                "Mutually exclusive feature '{}' is not in [features]",
+                feature
+            );
+        }
+    }
+
+    let candidates: Vec<&str> = graph
+        .features
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .filter(|name| *name != "default")
+        .collect();
+    let count = combinations(candidates.len(), depth);
+    if count > MAX_COMBINATIONS {
+        anyhow::bail!(
+            "{} features give {} combinations of up to {}; use a lower --depth",
+            candidates.len(),
+            count,
+            depth
+        );
+    }
+
+    let mut combos = Vec::new();
+    let mut seen = BTreeSet::new();
+    let mut current = Vec::new();
+    powerset(&candidates, 0, depth, &mut current, &mut |combo| {
+        let resolved = graph.resolve(combo.iter().copied());
+
+        let conflicting = groups
+            .iter()
+            .any(|group| group.iter().filter(|f| resolved.contains(*f)).count() > 1);
+
+        // A combo naming a feature another member already enables adds nothing
+        let redundant = combo.iter().any(|f| {
+            graph
+                .resolve(combo.iter().copied().filter(|o| o != f))
+                .contains(*f)
+        });
+
+        if !conflicting && !redundant && seen.insert(resolved) {
+            combos.push(combo.iter().map(|f| f.to_string()).collect::<Vec<_>>());
+        }
+    });
+
+    combos.sort_by_key(|combo| combo.len());
+    if matches!(format, MatrixFormat::Github) && combos.len() > GITHUB_MATRIX_LIMIT {
+        anyhow::bail!(
+            "The matrix has {} jobs but GitHub Actions allows at most {}; use a lower --depth or --exclusive groups",
+            combos.len(),
+            GITHUB_MATRIX_LIMIT
+        );
+    }
+
+    match format {
+        MatrixFormat::Json => {
+            let rows = combos
+                .iter()
+                .map(|combo| serde_json::to_string(combo).map(|row| format!("  {}", row)))
+                .collect::<Result<Vec<_>, _>>()?;
+            println!("[\n{}\n]", rows.join(",\n"));
+        }
+        MatrixFormat::Github => {
+            println!("strategy:");
+            println!("  matrix:");
+            println!("    features:");
+            for combo in &combos {
+                println!("      - \"{}\"", combo.join(","));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Number of subsets of `n` items with at most `depth` elements.
+fn combinations(n: usize, depth: usize) -> u128 {
+    let mut total: u128 = 0;
+    let mut choose: u128 = 1;
+    for k in 0..=depth.min(n) {
+        total = total.saturating_add(choose);
+        choose = choose.saturating_mul((n - k) as u128) / (k as u128 + 1);
+    }
+    total
+}
+
+/// Calls `visit` with every subset of `items[start..]` (added to `current`) of at
+/// most `depth` elements, smaller subsets first within each branch.
+fn powerset<'a>(
+    items: &[&'a str],
+    start: usize,
+    depth: usize,
+    current: &mut Vec<&'a str>,
+    visit: &mut dyn FnMut(&[&'a str]),
+) {
+    visit(current);
+    if current.len() == depth {
+        return;
+    }
+    for i in start..items.len() {
+        current.push(items[i]);
+        powerset(items, i + 1, depth, current, visit);
+        current.pop();
+    }
+}
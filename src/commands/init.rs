@@ -1,3 +1,4 @@
+use super::set::warn_url;
 use crate::manifest::Manifest;
 use crate::utils::{print_info, print_success};
 use anyhow::Result;
@@ -24,6 +25,7 @@ pub fn handle(path: &Path, yes: bool, dry_run: bool) -> Result<()> {
             .with_initial_text("https://github.com/user/repo")
             .interact_text()?;
 
+        warn_url("repository", &repo);
         if !dry_run {
             package.insert("repository", toml_edit::value(repo.clone()));
         }
@@ -40,6 +42,7 @@ pub fn handle(path: &Path, yes: bool, dry_run: bool) -> Result<()> {
     {
         let homepage: String = Input::new().with_prompt("Homepage URL").interact_text()?;
 
+        warn_url("homepage", &homepage);
         if !dry_run {
             package.insert("homepage", toml_edit::value(homepage.clone()));
         }
@@ -58,6 +61,7 @@ pub fn handle(path: &Path, yes: bool, dry_run: bool) -> Result<()> {
             .with_prompt("Documentation URL")
             .interact_text()?;
 
+        warn_url("documentation", &docs);
         if !dry_run {
             package.insert("documentation", toml_edit::value(docs.clone()));
         }
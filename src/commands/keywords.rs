@@ -25,34 +25,18 @@ fn add(path: &Path, keywords: Vec<String>, dry_run: bool, quiet: bool) -> Result
         .as_array_mut()
         .context("keywords is not an array")?;
 
-    let current_count = kw.len();
-
     for keyword in keywords {
-        // Validate keyword length
-        if keyword.len() > 20 {
-            print_warning(&format!(
-                "Keyword '{}' exceeds 20 characters (crates.io limit)",
-                keyword
-            ));
-            continue;
-        }
-
-        // Check for non-alphanumeric characters (except hyphens and underscores)
-        if !keyword
-            .chars()
-            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
-        {
-            print_warning(&format!(
-                "Keyword '{}' contains invalid characters. Only alphanumeric, hyphens, and underscores are allowed.",
-                keyword
-            ));
+        if let Err(e) = check_keyword(&keyword) {
+            print_warning(&e);
             continue;
         }
 
-        let exists = kw.iter().any(|v| v.as_str().is_some_and(|s| s == keyword));
+        let exists = kw
+            .iter()
+            .any(|v| v.as_str().is_some_and(|s| s.eq_ignore_ascii_case(&keyword)));
 
         if !exists {
-            if current_count >= 5 {
+            if kw.len() >= 5 {
                 print_warning("Maximum 5 keywords allowed for crates.io");
                 print_info(
                     &format!("Skipping '{}' and remaining keywords", keyword),
@@ -79,6 +63,40 @@ fn add(path: &Path, keywords: Vec<String>, dry_run: bool, quiet: bool) -> Result
     Ok(())
 }
 
+/// crates.io's keyword rules: ASCII letters, digits, `_`, `-` and `+`,
+/// starting with a letter, at most 20 characters.
+pub fn check_keyword(keyword: &str) -> Result<(), String> {
+    if keyword.len() > 20 {
+        return Err(format!(
+            "Keyword '{}' exceeds 20 characters (crates.io limit)",
+            keyword
+        ));
+    }
+    if !keyword.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(format!(
+            "Keyword '{}' must start with an ASCII letter",
+            keyword
+        ));
+    }
+    if !keyword
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '+'))
+    {
+        return Err(format!(
+            "Keyword '{}' contains invalid characters. Only ASCII letters, digits, '-', '_' and '+' are allowed.",
+            keyword
+        ));
+    }
+    Ok(())
+}
+
+/// The keyword crates.io would accept for `keyword`: lowercased, with spaces
+/// turned into hyphens. `None` if it still breaks the rules.
+pub fn normalize_keyword(keyword: &str) -> Option<String> {
+    let normalized = keyword.trim().to_lowercase().replace(' ', "-");
+    check_keyword(&normalized).ok().map(|_| normalized)
+}
+
 fn remove(path: &Path, keywords: Vec<String>, dry_run: bool, quiet: bool) -> Result<()> {
     let mut manifest = Manifest::load(path)?;
     let package = manifest.package_mut()?;
@@ -131,8 +149,14 @@ fn list(path: &Path) -> Result<()> {
                     println!("Keywords ({}/5):", arr.len());
                     for v in arr.iter() {
                         if let Some(s) = v.as_str() {
-                            let len_indicator = if s.len() > 20 { " ⚠ (too long)" } else { "" };
-                            println!("  • {}{}", s, len_indicator);
+                            let indicator = if s.len() > 20 {
+                                " ⚠ (too long)"
+                            } else if check_keyword(s).is_err() {
+                                " ⚠ (invalid)"
+                            } else {
+                                ""
+                            };
+                            println!("  • {}{}", s, indicator);
                         }
                     }
                 }
@@ -0,0 +1,581 @@
+// src/commands/lints.rs
+use crate::manifest::Manifest;
+use crate::msrv::{self, RustVersion};
+use crate::utils::{print_info, print_success, print_warning};
+use crate::{LintLevel, LintTool, LintsCommands};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{InlineTable, Item, Table, Value};
+
+/// Lint groups get `priority = -1` on migration so individual lints can override them.
+const LINT_GROUPS: &[&str] = &[
+    "all",
+    "cargo",
+    "complexity",
+    "correctness",
+    "nursery",
+    "pedantic",
+    "perf",
+    "restriction",
+    "style",
+    "suspicious",
+    "warnings",
+    "unused",
+    "future_incompatible",
+    "nonstandard_style",
+    "rust_2018_idioms",
+    "rust_2018_compatibility",
+    "rust_2021_compatibility",
+    "rust_2024_compatibility",
+    "let_underscore",
+    "keyword_idents",
+];
+
+impl LintTool {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LintTool::Rust => "rust",
+            LintTool::Clippy => "clippy",
+            LintTool::Rustdoc => "rustdoc",
+        }
+    }
+}
+
+impl LintLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+            LintLevel::Forbid => "forbid",
+        }
+    }
+}
+
+pub fn handle(path: &Path, cmd: LintsCommands, dry_run: bool, quiet: bool) -> Result<()> {
+    match cmd {
+        LintsCommands::Set {
+            tool,
+            lint,
+            level,
+            priority,
+            workspace,
+        } => set(
+            path, tool, &lint, level, priority, workspace, dry_run, quiet,
+        ),
+        LintsCommands::Remove {
+            tool,
+            lint,
+            workspace,
+        } => remove(path, tool, &lint, workspace, dry_run, quiet),
+        LintsCommands::List => list(path),
+        LintsCommands::Inherit => inherit(path, dry_run, quiet),
+        LintsCommands::Migrate { keep_attributes } => {
+            migrate(path, keep_attributes, dry_run, quiet)
+        }
+    }
+}
+
+/// Strips a `clippy::` / `rustdoc::` prefix that matches the tool.
+fn lint_name(tool: LintTool, lint: &str) -> String {
+    lint.strip_prefix(&format!("{}::", tool.as_str()))
+        .unwrap_or(lint)
+        .to_string()
+}
+
+fn display_lint(tool: &str, lint: &str) -> String {
+    if tool == "rust" {
+        lint.to_string()
+    } else {
+        format!("{}::{}", tool, lint)
+    }
+}
+
+fn lint_value(level: &str, priority: Option<i64>) -> Item {
+    match priority {
+        Some(priority) => {
+            let mut table = InlineTable::new();
+            table.insert("level", Value::from(level));
+            table.insert("priority", Value::from(priority));
+            Item::Value(Value::InlineTable(table))
+        }
+        None => toml_edit::value(level),
+    }
+}
+
+/// The level of an existing lint entry, written either as `"deny"` or as
+/// `{ level = "deny", priority = -1 }`.
+fn entry_level(item: &Item) -> Option<&str> {
+    item.as_str().or_else(|| {
+        item.as_table_like()
+            .and_then(|t| t.get("level"))
+            .and_then(|l| l.as_str())
+    })
+}
+
+fn lints_table_mut(manifest: &mut Manifest, workspace: bool) -> Result<&mut Table> {
+    let root = if workspace {
+        manifest
+            .doc
+            .get_mut("workspace")
+            .context("No [workspace] section found")?
+            .as_table_mut()
+            .context("workspace is not a table")?
+    } else {
+        manifest.doc.as_table_mut()
+    };
+    let lints = root
+        .entry("lints")
+        .or_insert_with(|| {
+            let mut t = Table::new();
+            t.set_implicit(true);
+            Item::Table(t)
+        })
+        .as_table_mut()
+        .context("lints is not a table")?;
+    Ok(lints)
+}
+
+fn tool_table_mut<'a>(lints: &'a mut Table, tool: &str) -> Result<&'a mut Table> {
+    lints
+        .entry(tool)
+        .or_insert(Item::Table(Table::new()))
+        .as_table_mut()
+        .with_context(|| format!("lints.{} is not a table", tool))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn set(
+    path: &Path,
+    tool: LintTool,
+    lint: &str,
+    level: LintLevel,
+    priority: Option<i64>,
+    workspace: bool,
+    dry_run: bool,
+    quiet: bool,
+) -> Result<()> {
+    let mut manifest = Manifest::load(path)?;
+    let lint = lint_name(tool, lint);
+
+    let lints = lints_table_mut(&mut manifest, workspace)?;
+    if !workspace && lints.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+        anyhow::bail!(
+            "[lints] inherits from the workspace (workspace = true); use --workspace to edit [workspace.lints]"
+        );
+    }
+
+    let section = if workspace {
+        "workspace.lints"
+    } else {
+        "lints"
+    };
+    if !dry_run {
+        tool_table_mut(lints, tool.as_str())?.insert(&lint, lint_value(level.as_str(), priority));
+        manifest.save()?;
+    }
+
+    let priority_info = priority
+        .map(|p| format!(" (priority {})", p))
+        .unwrap_or_default();
+    print_info(
+        &format!(
+            "[{}.{}] {} = \"{}\"{}",
+            section,
+            tool.as_str(),
+            lint,
+            level.as_str(),
+            priority_info
+        ),
+        quiet,
+    );
+    if !dry_run {
+        print_success("Lints updated", quiet);
+    } else {
+        print_info("(dry run - no changes made)", false);
+    }
+
+    Ok(())
+}
+
+fn remove(
+    path: &Path,
+    tool: LintTool,
+    lint: &str,
+    workspace: bool,
+    dry_run: bool,
+    quiet: bool,
+) -> Result<()> {
+    let mut manifest = Manifest::load(path)?;
+    let lint = lint_name(tool, lint);
+
+    let lints = lints_table_mut(&mut manifest, workspace)?;
+    let Some(tool_table) = lints
+        .get_mut(tool.as_str())
+        .and_then(|t| t.as_table_like_mut())
+    else {
+        print_info(
+            &format!("? {} (not found)", display_lint(tool.as_str(), &lint)),
+            quiet,
+        );
+        return Ok(());
+    };
+
+    if tool_table.contains_key(&lint) {
+        tool_table.remove(&lint);
+        if tool_table.is_empty() {
+            lints.remove(tool.as_str());
+        }
+        if !dry_run {
+            manifest.save()?;
+        }
+        print_info(&format!("- {}", display_lint(tool.as_str(), &lint)), quiet);
+        if !dry_run {
+            print_success("Lints updated", quiet);
+        } else {
+            print_info("(dry run - no changes made)", false);
+        }
+    } else {
+        print_info(
+            &format!("? {} (not found)", display_lint(tool.as_str(), &lint)),
+            quiet,
+        );
+    }
+
+    Ok(())
+}
+
+fn list(path: &Path) -> Result<()> {
+    let manifest = Manifest::load(path)?;
+
+    let sections = [
+        ("lints", manifest.doc.get("lints")),
+        (
+            "workspace.lints",
+            manifest.doc.get("workspace").and_then(|w| w.get("lints")),
+        ),
+    ];
+
+    let mut any = false;
+    for (section, lints) in sections {
+        let Some(lints) = lints.and_then(|l| l.as_table_like()) else {
+            continue;
+        };
+        any = true;
+        println!("[{}]", section);
+        for (tool, lints) in lints.iter() {
+            if let Some(inherit) = lints.as_bool() {
+                println!("  {} = {}", tool, inherit);
+                continue;
+            }
+            let Some(lints) = lints.as_table_like() else {
+                continue;
+            };
+            for (lint, value) in lints.iter() {
+                let level = entry_level(value).unwrap_or("?");
+                let priority = value
+                    .as_table_like()
+                    .and_then(|t| t.get("priority"))
+                    .and_then(|p| p.as_integer())
+                    .map(|p| format!(" (priority {})", p))
+                    .unwrap_or_default();
+                println!("  {} = {}{}", display_lint(tool, lint), level, priority);
+            }
+        }
+    }
+
+    if !any {
+        println!("No lints configured");
+    }
+    Ok(())
+}
+
+fn inherit(path: &Path, dry_run: bool, quiet: bool) -> Result<()> {
+    let mut manifest = Manifest::load(path)?;
+
+    if let Some(lints) = manifest.doc.get("lints").and_then(|l| l.as_table_like()) {
+        if lints.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+            print_info("[lints] already inherits from the workspace", quiet);
+            return Ok(());
+        }
+        if lints.iter().any(|(k, _)| k != "workspace") {
+            anyhow::bail!(
+                "[lints] already configures lints; move them to [workspace.lints] before inheriting"
+            );
+        }
+    }
+
+    if !dry_run {
+        let mut lints = Table::new();
+        lints.insert("workspace", toml_edit::value(true));
+        manifest.doc.insert("lints", Item::Table(lints));
+        manifest.save()?;
+        print_success("[lints] now inherits from [workspace.lints]", quiet);
+    } else {
+        print_info("[lints] workspace = true", quiet);
+        print_info("(dry run - no changes made)", false);
+    }
+
+    Ok(())
+}
+
+/// A crate-level `#![level(lint, ...)]` attribute found in a source file.
+struct LintAttribute {
+    range: std::ops::Range<usize>,
+    level: String,
+    lints: Vec<String>,
+}
+
+/// Finds lint attributes among the inner attributes at the top of a crate root.
+fn parse_lint_attributes(source: &str) -> Vec<LintAttribute> {
+    let mut attrs = Vec::new();
+    let mut pos = 0;
+
+    while pos < source.len() {
+        let rest = &source[pos..];
+        let trimmed = rest.trim_start();
+        let start = pos + (rest.len() - trimmed.len());
+
+        if trimmed.starts_with("//") {
+            pos = start + trimmed.find('\n').map(|i| i + 1).unwrap_or(trimmed.len());
+            continue;
+        }
+        if !trimmed.starts_with("#![") {
+            break;
+        }
+
+        // Find the closing bracket of this attribute
+        let mut depth = 0;
+        let mut end = None;
+        for (i, c) in trimmed.char_indices().skip(2) {
+            match c {
+                '[' | '(' => depth += 1,
+                ']' | ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(i + 1);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let Some(end) = end else { break };
+
+        let inner = trimmed[3..end - 1].trim();
+        if let Some((level, list)) = inner.split_once('(') {
+            let level = level.trim();
+            if ["allow", "warn", "deny", "forbid"].contains(&level) && !list.contains('=') {
+                let lints: Vec<String> = list
+                    .trim_end_matches(')')
+                    .split(',')
+                    .map(|l| l.trim().to_string())
+                    .filter(|l| !l.is_empty())
+                    .collect();
+                attrs.push(LintAttribute {
+                    range: start..start + end,
+                    level: level.to_string(),
+                    lints,
+                });
+            }
+        }
+        pos = start + end;
+    }
+
+    attrs
+}
+
+/// Splits `clippy::foo` into the `[lints]` tool table and lint name.
+fn split_tool(lint: &str) -> (&str, &str) {
+    match lint.split_once("::") {
+        Some((tool @ ("clippy" | "rustdoc"), name)) => (tool, name),
+        _ => ("rust", lint),
+    }
+}
+
+fn migrate(path: &Path, keep_attributes: bool, dry_run: bool, quiet: bool) -> Result<()> {
+    let mut manifest = Manifest::load(path)?;
+    let root = path.parent().unwrap_or(Path::new("."));
+
+    if manifest
+        .doc
+        .get("lints")
+        .and_then(|l| l.get("workspace"))
+        .and_then(|w| w.as_bool())
+        == Some(true)
+    {
+        anyhow::bail!(
+            "[lints] inherits from the workspace; migrate into [workspace.lints] instead"
+        );
+    }
+
+    let lib_path = manifest
+        .doc
+        .get("lib")
+        .and_then(|l| l.get("path"))
+        .and_then(|p| p.as_str())
+        .unwrap_or("src/lib.rs")
+        .to_string();
+    let crate_roots: Vec<PathBuf> = [lib_path.as_str(), "src/main.rs"]
+        .iter()
+        .map(|p| root.join(p))
+        .filter(|p| p.is_file())
+        .collect();
+    if crate_roots.is_empty() {
+        anyhow::bail!("No src/lib.rs or src/main.rs found");
+    }
+
+    let mut sources = Vec::new();
+    for file in &crate_roots {
+        let source = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let attrs = parse_lint_attributes(&source);
+        sources.push((file, source, attrs));
+    }
+
+    // Collect every (lint, level) and drop lints whose level differs between
+    // crate roots or from what the manifest already says. [lints] applies to
+    // every target, so a lint set in only one crate root stays there too.
+    let mut planned: Vec<(String, String)> = Vec::new();
+    let mut conflicting: Vec<String> = Vec::new();
+    for (_, _, attrs) in &sources {
+        for attr in attrs {
+            for lint in &attr.lints {
+                match planned.iter().find(|(l, _)| l == lint) {
+                    Some((_, level)) if *level != attr.level => conflicting.push(lint.clone()),
+                    Some(_) => {}
+                    None => planned.push((lint.clone(), attr.level.clone())),
+                }
+            }
+        }
+    }
+    for (lint, _) in &planned {
+        let everywhere = sources
+            .iter()
+            .all(|(_, _, attrs)| attrs.iter().any(|a| a.lints.contains(lint)));
+        if !everywhere {
+            conflicting.push(lint.clone());
+        }
+    }
+    for (lint, level) in &planned {
+        let (tool, name) = split_tool(lint);
+        let existing = manifest
+            .doc
+            .get("lints")
+            .and_then(|l| l.get(tool))
+            .and_then(|t| t.get(name))
+            .and_then(entry_level);
+        if existing.is_some_and(|e| e != level) {
+            conflicting.push(lint.clone());
+        }
+    }
+    planned.retain(|(lint, _)| !conflicting.contains(lint));
+    conflicting.sort();
+    conflicting.dedup();
+
+    if planned.is_empty() && conflicting.is_empty() {
+        print_info("No crate-level lint attributes found", quiet);
+        return Ok(());
+    }
+
+    // Older toolchains ignore [lints], so the attributes must stay for them
+    let rust_version = manifest
+        .package()
+        .and_then(|p| p.get("rust-version"))
+        .and_then(|v| v.as_str())
+        .and_then(|v| RustVersion::parse(v).ok());
+    let mut keep_attributes = keep_attributes;
+    if let Some(version) = rust_version.filter(|v| *v < msrv::LINTS_TABLE) {
+        if !planned.is_empty() && !keep_attributes {
+            print_warning(&format!(
+                "rust-version {} is lower than {} required by the [lints] table; keeping the attributes in source",
+                version,
+                msrv::LINTS_TABLE
+            ));
+            keep_attributes = true;
+        }
+    }
+
+    {
+        let lints = lints_table_mut(&mut manifest, false)?;
+        for (lint, level) in &planned {
+            let (tool, name) = split_tool(lint);
+            let priority = LINT_GROUPS.contains(&name).then_some(-1);
+            tool_table_mut(lints, tool)?.insert(name, lint_value(level, priority));
+            print_info(
+                &format!("+ [lints.{}] {} = \"{}\"", tool, name, level),
+                quiet,
+            );
+        }
+    }
+    for lint in &conflicting {
+        print_warning(&format!(
+            "'{}' is not set at the same level in every crate root and the manifest; left in source",
+            lint
+        ));
+    }
+
+    if !dry_run {
+        manifest.save()?;
+    }
+
+    if !keep_attributes {
+        for (file, source, attrs) in &sources {
+            let updated = remove_migrated(source, attrs, &planned);
+            if updated != *source {
+                let display = file
+                    .strip_prefix(root)
+                    .unwrap_or(file)
+                    .display()
+                    .to_string();
+                if !dry_run {
+                    fs::write(file, updated)
+                        .with_context(|| format!("Failed to write {}", file.display()))?;
+                }
+                print_info(&format!("~ {} (lint attributes removed)", display), quiet);
+            }
+        }
+    }
+
+    if !dry_run {
+        print_success("Lint attributes migrated to [lints]", quiet);
+    } else {
+        print_info("(dry run - no changes made)", false);
+    }
+
+    Ok(())
+}
+
+/// Rewrites `source` without the migrated lints, dropping attributes that end up empty.
+fn remove_migrated(source: &str, attrs: &[LintAttribute], migrated: &[(String, String)]) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+
+    for attr in attrs {
+        let remaining: Vec<&str> = attr
+            .lints
+            .iter()
+            .filter(|l| !migrated.iter().any(|(m, _)| m == *l))
+            .map(|l| l.as_str())
+            .collect();
+        if remaining.len() == attr.lints.len() {
+            continue;
+        }
+
+        out.push_str(&source[last..attr.range.start]);
+        last = attr.range.end;
+        if remaining.is_empty() {
+            // Take the rest of the line with it
+            if source[last..].starts_with("\r\n") {
+                last += 2;
+            } else if source[last..].starts_with('\n') {
+                last += 1;
+            }
+        } else {
+            out.push_str(&format!("#![{}({})]", attr.level, remaining.join(", ")));
+        }
+    }
+    out.push_str(&source[last..]);
+    out
+}
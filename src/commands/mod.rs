@@ -1,13 +1,20 @@
 // src/commands/mod.rs
 pub mod badges;
 pub mod categories;
+pub mod changelog;
 pub mod exclude;
+pub mod features;
 pub mod fmt;
 pub mod get;
 pub mod include;
 pub mod info;
 pub mod init;
 pub mod keywords;
+pub mod lints;
 pub mod metadata;
+pub mod package;
+pub mod profile;
 pub mod set;
+pub mod targets;
 pub mod validate;
+pub mod version;
@@ -0,0 +1,136 @@
+// src/commands/package.rs
+use crate::manifest::Manifest;
+use crate::patterns::{matches_any, walk_files, Pattern};
+use crate::utils::{format_size, print_warning};
+use crate::PackageCommands;
+use anyhow::{anyhow, Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Files Cargo picks up as the readme when `readme` is not set.
+const DEFAULT_READMES: &[&str] = &["README.md", "README.txt", "README"];
+
+pub fn handle(path: &Path, cmd: PackageCommands) -> Result<()> {
+    match cmd {
+        PackageCommands::List => list(path),
+    }
+}
+
+/// The files `cargo package` would put in the crate, relative to the package
+/// root. With `include`, only matching files ship and `exclude` is ignored;
+/// otherwise everything not excluded (or ignored by `.gitignore`) ships.
+/// `Cargo.toml`, `Cargo.lock`, the readme and the license file are always
+/// included.
+pub fn package_files(manifest: &Manifest) -> Result<Vec<String>> {
+    let package = manifest.package().context("No [package] section found")?;
+    let root = manifest.path.parent().unwrap_or(Path::new("."));
+
+    let patterns = |key: &str| -> Result<Vec<Pattern>> {
+        let Some(array) = package.get(key).and_then(|v| v.as_array()) else {
+            return Ok(Vec::new());
+        };
+        array
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|p| Pattern::new(p).map_err(|e| anyhow!("Invalid '{}' entry: {}", key, e)))
+            .collect()
+    };
+    let include = patterns("include")?;
+    let exclude = patterns("exclude")?;
+    let ignored = if include.is_empty() {
+        gitignore(root)
+    } else {
+        Vec::new()
+    };
+
+    let mut always = vec!["Cargo.toml".to_string(), "Cargo.lock".to_string()];
+    match package.get("readme") {
+        Some(readme) => always.extend(readme.as_str().map(relative)),
+        None => always.extend(
+            DEFAULT_READMES
+                .iter()
+                .find(|r| root.join(r).is_file())
+                .map(|r| r.to_string()),
+        ),
+    }
+    always.extend(
+        package
+            .get("license-file")
+            .and_then(|v| v.as_str())
+            .map(relative),
+    );
+
+    let files = walk_files(root)
+        .into_iter()
+        .filter(|file| {
+            if always.contains(file) {
+                true
+            } else if !include.is_empty() {
+                matches_any(&include, file).is_some()
+            } else {
+                matches_any(&exclude, file).is_none() && matches_any(&ignored, file).is_none()
+            }
+        })
+        .collect();
+    Ok(files)
+}
+
+fn relative(path: &str) -> String {
+    path.trim_start_matches("./").replace('\\', "/")
+}
+
+/// Patterns from the package's `.gitignore`; Cargo honours them when the
+/// package is in a git repository and has no `include`.
+fn gitignore(root: &Path) -> Vec<Pattern> {
+    let Ok(content) = fs::read_to_string(root.join(".gitignore")) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| Pattern::new(line).ok())
+        .collect()
+}
+
+fn list(path: &Path) -> Result<()> {
+    let manifest = Manifest::load(path)?;
+    let package = manifest.package().context("No [package] section found")?;
+    if package.contains_key("include") && package.contains_key("exclude") {
+        print_warning("Both 'include' and 'exclude' are set; 'exclude' is ignored");
+    }
+
+    let root = manifest.path.parent().unwrap_or(Path::new("."));
+    let files = package_files(&manifest)?;
+
+    // Approximates the .crate archive: tar entries (512-byte header, content
+    // padded to 512 bytes) compressed with gzip.
+    let mut archive = GzEncoder::new(Vec::new(), Compression::default());
+    let mut total = 0;
+    for file in &files {
+        let content = fs::read(root.join(file))
+            .with_context(|| format!("Failed to read {}", root.join(file).display()))?;
+        total += content.len();
+        println!("{:>10}  {}", format_size(content.len() as u64), file);
+
+        let mut header = [0u8; 512];
+        let name = &file.as_bytes()[..file.len().min(100)];
+        header[..name.len()].copy_from_slice(name);
+        archive.write_all(&header)?;
+        archive.write_all(&content)?;
+        archive.write_all(&vec![0; (512 - content.len() % 512) % 512])?;
+    }
+    let compressed = archive.finish()?.len();
+
+    println!();
+    println!(
+        "{} file(s), {} ({} compressed, estimated)",
+        files.len(),
+        format_size(total as u64),
+        format_size(compressed as u64)
+    );
+    Ok(())
+}
@@ -0,0 +1,407 @@
+// src/commands/profile.rs
+use crate::manifest::Manifest;
+use crate::utils::{print_info, print_success, print_warning};
+use crate::{ProfileCommands, ProfilePreset};
+use anyhow::{Context, Result};
+use std::path::Path;
+use toml_edit::{Item, Table, Value};
+
+const BUILTIN_PROFILES: &[&str] = &["dev", "release", "test", "bench"];
+
+/// Values accepted for a profile key.
+enum Allowed {
+    Bool,
+    Ints(&'static [i64]),
+    PositiveInt,
+    Strs(&'static [&'static str]),
+    AnyStr,
+}
+
+const PROFILE_KEYS: &[(&str, &[Allowed])] = &[
+    (
+        "opt-level",
+        &[Allowed::Ints(&[0, 1, 2, 3]), Allowed::Strs(&["s", "z"])],
+    ),
+    (
+        "debug",
+        &[
+            Allowed::Bool,
+            Allowed::Ints(&[0, 1, 2]),
+            Allowed::Strs(&[
+                "none",
+                "line-directives-only",
+                "line-tables-only",
+                "limited",
+                "full",
+            ]),
+        ],
+    ),
+    (
+        "split-debuginfo",
+        &[Allowed::Strs(&["off", "packed", "unpacked"])],
+    ),
+    (
+        "strip",
+        &[
+            Allowed::Bool,
+            Allowed::Strs(&["none", "debuginfo", "symbols"]),
+        ],
+    ),
+    ("debug-assertions", &[Allowed::Bool]),
+    ("overflow-checks", &[Allowed::Bool]),
+    (
+        "lto",
+        &[Allowed::Bool, Allowed::Strs(&["fat", "thin", "off"])],
+    ),
+    ("panic", &[Allowed::Strs(&["unwind", "abort"])]),
+    ("incremental", &[Allowed::Bool]),
+    ("codegen-units", &[Allowed::PositiveInt]),
+    ("rpath", &[Allowed::Bool]),
+    ("inherits", &[Allowed::AnyStr]),
+];
+
+pub fn handle(path: &Path, cmd: ProfileCommands, dry_run: bool, quiet: bool) -> Result<()> {
+    match cmd {
+        ProfileCommands::List => list(path),
+        ProfileCommands::Set {
+            profile,
+            key,
+            value,
+        } => set(path, &profile, &key, &value, dry_run, quiet),
+        ProfileCommands::Unset { profile, key } => unset(path, &profile, &key, dry_run, quiet),
+        ProfileCommands::Create { profile, inherits } => {
+            create(path, &profile, &inherits, dry_run, quiet)
+        }
+        ProfileCommands::Remove { profile } => remove(path, &profile, dry_run, quiet),
+        ProfileCommands::Preset { preset, profile } => {
+            apply_preset(path, preset, profile, dry_run, quiet)
+        }
+    }
+}
+
+pub fn is_profile_key(key: &str) -> bool {
+    PROFILE_KEYS.iter().any(|(k, _)| *k == key)
+}
+
+/// Checks a profile value against the keys cargo understands. Returns a
+/// description of the accepted values when it does not fit.
+pub fn check_profile_value(key: &str, value: &Value) -> Result<(), String> {
+    let Some((_, allowed)) = PROFILE_KEYS.iter().find(|(k, _)| *k == key) else {
+        return Err(format!(
+            "unknown profile key (valid keys: {})",
+            PROFILE_KEYS
+                .iter()
+                .map(|(k, _)| *k)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    };
+
+    let fits = allowed.iter().any(|a| match (a, value) {
+        (Allowed::Bool, Value::Boolean(_)) => true,
+        (Allowed::Ints(ints), Value::Integer(i)) => ints.contains(i.value()),
+        (Allowed::PositiveInt, Value::Integer(i)) => *i.value() > 0,
+        (Allowed::Strs(strs), Value::String(s)) => strs.contains(&s.value().as_str()),
+        (Allowed::AnyStr, Value::String(_)) => true,
+        _ => false,
+    });
+    if fits {
+        return Ok(());
+    }
+
+    let expected: Vec<String> = allowed
+        .iter()
+        .map(|a| match a {
+            Allowed::Bool => "true/false".to_string(),
+            Allowed::Ints(ints) => ints
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join("/"),
+            Allowed::PositiveInt => "a positive integer".to_string(),
+            Allowed::Strs(strs) => strs
+                .iter()
+                .map(|s| format!("\"{}\"", s))
+                .collect::<Vec<_>>()
+                .join("/"),
+            Allowed::AnyStr => "a string".to_string(),
+        })
+        .collect();
+    Err(format!("expected {}", expected.join(" or ")))
+}
+
+/// Parses a command-line value as a TOML value, falling back to a plain string so
+/// `z` and `"z"` mean the same thing.
+fn parse_value(raw: &str) -> Value {
+    raw.parse::<Value>()
+        .map(|mut v| {
+            v.decor_mut().clear();
+            v
+        })
+        .unwrap_or_else(|_| Value::from(raw))
+}
+
+fn list(path: &Path) -> Result<()> {
+    let manifest = Manifest::load(path)?;
+
+    let Some(profiles) = manifest.doc.get("profile").and_then(|p| p.as_table_like()) else {
+        println!("No profiles defined");
+        return Ok(());
+    };
+    if profiles.is_empty() {
+        println!("No profiles defined");
+        return Ok(());
+    }
+
+    println!("Profiles:");
+    for (name, profile) in profiles.iter() {
+        let kind = if BUILTIN_PROFILES.contains(&name) {
+            ""
+        } else {
+            " (custom)"
+        };
+        println!("\n  [profile.{}]{}", name, kind);
+        if let Some(table) = profile.as_table_like() {
+            for (key, value) in table.iter() {
+                match value.as_value() {
+                    Some(v) => println!("    {} = {}", key, v.to_string().trim()),
+                    None => println!("    {} = {{ ... }}", key),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn set(path: &Path, profile: &str, key: &str, raw: &str, dry_run: bool, quiet: bool) -> Result<()> {
+    let value = parse_value(raw);
+    check_profile_value(key, &value)
+        .map_err(|e| anyhow::anyhow!("Invalid value '{}' for '{}': {}", raw, key, e))?;
+
+    if key == "inherits" && BUILTIN_PROFILES.contains(&profile) {
+        anyhow::bail!("Built-in profile '{}' cannot set 'inherits'", profile);
+    }
+
+    let mut manifest = Manifest::load(path)?;
+
+    let is_custom = !BUILTIN_PROFILES.contains(&profile);
+    let has_inherits =
+        profile_table(&manifest, profile).is_some_and(|t| t.contains_key("inherits"));
+    if is_custom && !has_inherits && key != "inherits" {
+        anyhow::bail!(
+            "Custom profile '{}' needs 'inherits' first. Create it with: cargoe profile create {} --inherits release",
+            profile,
+            profile
+        );
+    }
+
+    if !dry_run {
+        profile_table_mut(&mut manifest, profile)?.insert(key, Item::Value(value.clone()));
+        manifest.save()?;
+    }
+
+    print_info(&format!("[profile.{}] {} = {}", profile, key, value), quiet);
+    if !dry_run {
+        print_success(&format!("Profile '{}' updated", profile), quiet);
+    } else {
+        print_info("(dry run - no changes made)", false);
+    }
+
+    Ok(())
+}
+
+fn unset(path: &Path, profile: &str, key: &str, dry_run: bool, quiet: bool) -> Result<()> {
+    let mut manifest = Manifest::load(path)?;
+
+    if !profile_table(&manifest, profile).is_some_and(|t| t.contains_key(key)) {
+        print_info(&format!("[profile.{}] '{}' not set", profile, key), quiet);
+        return Ok(());
+    }
+
+    if !dry_run {
+        profile_table_mut(&mut manifest, profile)?.remove(key);
+        manifest.save()?;
+    }
+    print_success(&format!("[profile.{}] '{}' removed", profile, key), quiet);
+
+    Ok(())
+}
+
+fn create(path: &Path, profile: &str, inherits: &str, dry_run: bool, quiet: bool) -> Result<()> {
+    if BUILTIN_PROFILES.contains(&profile) {
+        anyhow::bail!("'{}' is a built-in profile", profile);
+    }
+    if !profile
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!(
+            "Invalid profile name '{}'. Only alphanumeric, hyphens, and underscores are allowed.",
+            profile
+        );
+    }
+
+    let mut manifest = Manifest::load(path)?;
+
+    if profile_table(&manifest, profile).is_some() {
+        anyhow::bail!("Profile '{}' already exists", profile);
+    }
+    if !BUILTIN_PROFILES.contains(&inherits) && profile_table(&manifest, inherits).is_none() {
+        print_warning(&format!(
+            "Profile '{}' to inherit from is not defined",
+            inherits
+        ));
+    }
+
+    if !dry_run {
+        profile_table_mut(&mut manifest, profile)?.insert("inherits", toml_edit::value(inherits));
+        manifest.save()?;
+    }
+    print_info(
+        &format!("+ [profile.{}] inherits = \"{}\"", profile, inherits),
+        quiet,
+    );
+
+    if !dry_run {
+        print_success(&format!("Profile '{}' created", profile), quiet);
+    } else {
+        print_info("(dry run - no changes made)", false);
+    }
+
+    Ok(())
+}
+
+fn remove(path: &Path, profile: &str, dry_run: bool, quiet: bool) -> Result<()> {
+    let mut manifest = Manifest::load(path)?;
+
+    let Some(profiles) = manifest
+        .doc
+        .get_mut("profile")
+        .and_then(|p| p.as_table_like_mut())
+    else {
+        print_info(&format!("Profile '{}' not found", profile), quiet);
+        return Ok(());
+    };
+
+    if profiles.contains_key(profile) {
+        if !dry_run {
+            profiles.remove(profile);
+            if profiles.is_empty() {
+                manifest.doc.remove("profile");
+            }
+            manifest.save()?;
+        }
+        print_success(&format!("Profile '{}' removed", profile), quiet);
+    } else {
+        print_info(&format!("Profile '{}' not found", profile), quiet);
+    }
+
+    Ok(())
+}
+
+fn apply_preset(
+    path: &Path,
+    preset: ProfilePreset,
+    profile: Option<String>,
+    dry_run: bool,
+    quiet: bool,
+) -> Result<()> {
+    let (default_profile, settings): (&str, Vec<(&str, Value)>) = match preset {
+        ProfilePreset::MinSize => (
+            "release",
+            vec![
+                ("opt-level", Value::from("z")),
+                ("lto", Value::from(true)),
+                ("codegen-units", Value::from(1)),
+                ("strip", Value::from(true)),
+                ("panic", Value::from("abort")),
+            ],
+        ),
+        ProfilePreset::MaxPerf => (
+            "release",
+            vec![
+                ("opt-level", Value::from(3)),
+                ("lto", Value::from("fat")),
+                ("codegen-units", Value::from(1)),
+                ("incremental", Value::from(false)),
+            ],
+        ),
+        ProfilePreset::FastDebug => (
+            "dev",
+            vec![
+                ("opt-level", Value::from(1)),
+                ("debug", Value::from("line-tables-only")),
+                ("incremental", Value::from(true)),
+            ],
+        ),
+    };
+    let profile = profile.unwrap_or_else(|| default_profile.to_string());
+
+    let mut manifest = Manifest::load(path)?;
+
+    if !BUILTIN_PROFILES.contains(&profile.as_str())
+        && !profile_table(&manifest, &profile).is_some_and(|t| t.contains_key("inherits"))
+    {
+        anyhow::bail!(
+            "Custom profile '{}' needs 'inherits' first. Create it with: cargoe profile create {} --inherits {}",
+            profile,
+            profile,
+            default_profile
+        );
+    }
+
+    let table = profile_table_mut(&mut manifest, &profile)?;
+    for (key, value) in settings {
+        let previous = table
+            .get(key)
+            .and_then(|v| v.as_value())
+            .map(|v| v.to_string());
+        match previous {
+            Some(prev) if prev.trim() == value.to_string().trim() => {
+                print_info(&format!("~ {} = {} (unchanged)", key, value), quiet);
+                continue;
+            }
+            Some(prev) => print_info(
+                &format!("~ {} = {} (was {})", key, value, prev.trim()),
+                quiet,
+            ),
+            None => print_info(&format!("+ {} = {}", key, value), quiet),
+        }
+        table.insert(key, Item::Value(value));
+    }
+
+    if !dry_run {
+        manifest.save()?;
+        print_success(&format!("Applied preset to [profile.{}]", profile), quiet);
+    } else {
+        print_info("(dry run - no changes made)", false);
+    }
+
+    Ok(())
+}
+
+fn profile_table<'a>(
+    manifest: &'a Manifest,
+    profile: &str,
+) -> Option<&'a dyn toml_edit::TableLike> {
+    manifest.doc.get("profile")?.get(profile)?.as_table_like()
+}
+
+fn profile_table_mut<'a>(manifest: &'a mut Manifest, profile: &str) -> Result<&'a mut Table> {
+    let profiles = manifest
+        .doc
+        .entry("profile")
+        .or_insert_with(|| {
+            let mut t = Table::new();
+            t.set_implicit(true);
+            Item::Table(t)
+        })
+        .as_table_mut()
+        .context("profile is not a table")?;
+    profiles
+        .entry(profile)
+        .or_insert(Item::Table(Table::new()))
+        .as_table_mut()
+        .with_context(|| format!("profile.{} is not a table", profile))
+}
@@ -1,7 +1,10 @@
 // src/commands/set.rs
 use crate::manifest::Manifest;
+use crate::schema;
+use crate::spdx;
+use crate::urls;
 use crate::utils::{print_info, print_success, print_warning};
-use anyhow::Result;
+use anyhow::{bail, Result};
 use std::path::Path;
 
 const VALID_FIELDS: &[&str] = &[
@@ -18,8 +21,50 @@ const VALID_FIELDS: &[&str] = &[
     "version",
 ];
 
-pub fn handle(path: &Path, field: &str, value: &str, dry_run: bool, quiet: bool) -> Result<()> {
-    if !VALID_FIELDS.contains(&field) {
+const URL_FIELDS: &[&str] = &["repository", "homepage", "documentation"];
+
+pub fn handle(
+    path: &Path,
+    field: &str,
+    value: &str,
+    normalize: bool,
+    dry_run: bool,
+    quiet: bool,
+) -> Result<()> {
+    let normalized;
+    let value = if normalize {
+        if field != "repository" {
+            bail!("--normalize only applies to 'repository'");
+        }
+        normalized = urls::normalize_repository(value).map_err(anyhow::Error::msg)?;
+        normalized.as_str()
+    } else {
+        value
+    };
+
+    if URL_FIELDS.contains(&field) {
+        warn_url(field, value);
+    }
+
+    if field == "license" {
+        let report = spdx::check(value);
+        for problem in report.errors.iter().chain(&report.warnings) {
+            print_warning(problem);
+        }
+    }
+
+    if !schema::PACKAGE_KEYS.contains(&field) {
+        match schema::suggest(schema::PACKAGE_KEYS, field) {
+            Some(suggestion) => print_warning(&format!(
+                "Unknown package field '{}'; did you mean '{}'?",
+                field, suggestion
+            )),
+            None => {
+                print_warning(&format!("Unknown package field '{}'", field));
+                println!("Valid fields: {}", VALID_FIELDS.join(", "));
+            }
+        }
+    } else if !VALID_FIELDS.contains(&field) {
         print_warning(&format!(
             "Field '{}' is not a commonly set package field",
             field
@@ -90,3 +135,11 @@ pub fn handle(path: &Path, field: &str, value: &str, dry_run: bool, quiet: bool)
 
     Ok(())
 }
+
+/// Prints any problems with a link field without rejecting the value.
+pub fn warn_url(field: &str, value: &str) {
+    match urls::check(field, value) {
+        Ok(warnings) => warnings.iter().for_each(|w| print_warning(w)),
+        Err(e) => print_warning(&e),
+    }
+}
@@ -0,0 +1,417 @@
+// src/commands/targets.rs
+use crate::build_targets::{
+    auto_discovery_enabled, default_target_path, discovered_targets, explicit_targets,
+    target_entries, TargetKind,
+};
+use crate::feature_graph::FeatureGraph;
+use crate::manifest::Manifest;
+use crate::utils::{print_info, print_success, print_warning};
+use crate::TargetsCommands;
+use anyhow::{Context, Result};
+use std::path::Path;
+use toml_edit::{Array, ArrayOfTables, InlineTable, Item, Table, TableLike, Value};
+
+const EDITABLE_KEYS: &[&str] = &["path", "required-features", "harness", "crate-type", "doc"];
+
+const CRATE_TYPES: &[&str] = &[
+    "bin",
+    "lib",
+    "rlib",
+    "dylib",
+    "cdylib",
+    "staticlib",
+    "proc-macro",
+];
+
+pub fn handle(path: &Path, cmd: TargetsCommands, dry_run: bool, quiet: bool) -> Result<()> {
+    match cmd {
+        TargetsCommands::Add {
+            kind,
+            name,
+            path: target_path,
+        } => add(path, kind, &name, target_path, dry_run, quiet),
+        TargetsCommands::Remove { kind, name } => remove(path, kind, &name, dry_run, quiet),
+        TargetsCommands::List => list(path),
+        TargetsCommands::Set {
+            kind,
+            name,
+            key,
+            value,
+        } => set(path, kind, &name, &key, &value, dry_run, quiet),
+        TargetsCommands::Unset { kind, name, key } => {
+            unset(path, kind, &name, &key, dry_run, quiet)
+        }
+    }
+}
+
+fn add(
+    path: &Path,
+    kind: TargetKind,
+    name: &str,
+    target_path: Option<String>,
+    dry_run: bool,
+    quiet: bool,
+) -> Result<()> {
+    let mut manifest = Manifest::load(path)?;
+
+    if explicit_targets(&manifest)
+        .iter()
+        .any(|t| t.kind == kind && (kind == TargetKind::Lib || t.name == name))
+    {
+        anyhow::bail!("{} target '{}' is already declared", kind.section(), name);
+    }
+
+    let mut entry = Table::new();
+    entry.insert("name", toml_edit::value(name));
+    if let Some(p) = &target_path {
+        entry.insert("path", toml_edit::value(p.as_str()));
+    }
+
+    let shown_path = target_path
+        .clone()
+        .unwrap_or_else(|| default_target_path(&manifest, kind, name));
+    let root = path.parent().unwrap_or(Path::new("."));
+    if !root.join(&shown_path).exists() {
+        print_warning(&format!("Target path '{}' does not exist yet", shown_path));
+    }
+
+    if !dry_run {
+        push_entry(&mut manifest, kind, entry)?;
+        manifest.save()?;
+    }
+    print_info(
+        &format!("+ [{}] {} → {}", kind.section(), name, shown_path),
+        quiet,
+    );
+
+    if !dry_run {
+        print_success("Targets updated", quiet);
+    } else {
+        print_info("(dry run - no changes made)", false);
+    }
+
+    Ok(())
+}
+
+fn remove(path: &Path, kind: TargetKind, name: &str, dry_run: bool, quiet: bool) -> Result<()> {
+    let mut manifest = Manifest::load(path)?;
+    let section = kind.section();
+
+    let removed = match manifest.doc.get_mut(section) {
+        None => false,
+        Some(_) if kind == TargetKind::Lib => {
+            if !dry_run {
+                manifest.doc.remove(section);
+            }
+            true
+        }
+        Some(Item::ArrayOfTables(tables)) => {
+            let position = tables
+                .iter()
+                .position(|t| t.get("name").and_then(|n| n.as_str()) == Some(name));
+            if let (Some(i), false) = (position, dry_run) {
+                tables.remove(i);
+            }
+            position.is_some()
+        }
+        Some(Item::Value(Value::Array(array))) => {
+            let position = array.iter().position(|v| {
+                v.as_inline_table()
+                    .and_then(|t| t.get("name"))
+                    .and_then(|n| n.as_str())
+                    == Some(name)
+            });
+            if let (Some(i), false) = (position, dry_run) {
+                array.remove(i);
+            }
+            position.is_some()
+        }
+        Some(_) => anyhow::bail!("{} is not an array of tables", section),
+    };
+
+    if !removed {
+        print_info(&format!("? [{}] {} (not found)", section, name), quiet);
+        return Ok(());
+    }
+
+    let now_empty = match manifest.doc.get(section) {
+        Some(Item::ArrayOfTables(tables)) => tables.is_empty(),
+        Some(Item::Value(Value::Array(array))) => array.is_empty(),
+        _ => false,
+    };
+    if now_empty {
+        manifest.doc.remove(section);
+    }
+
+    print_info(&format!("- [{}] {}", section, name), quiet);
+    if !dry_run {
+        manifest.save()?;
+        print_success("Targets updated", quiet);
+    } else {
+        print_info("(dry run - no changes made)", false);
+    }
+
+    Ok(())
+}
+
+fn list(path: &Path) -> Result<()> {
+    let manifest = Manifest::load(path)?;
+    let explicit = explicit_targets(&manifest);
+    let discovered = discovered_targets(&manifest);
+
+    if explicit.is_empty() && discovered.is_empty() {
+        println!("No build targets");
+        return Ok(());
+    }
+
+    println!("Build targets:");
+    for kind in TargetKind::ALL {
+        let entries = target_entries(&manifest, kind);
+        for (target, entry) in explicit.iter().filter(|t| t.kind == kind).zip(entries) {
+            let target_path = target
+                .path
+                .clone()
+                .unwrap_or_else(|| default_target_path(&manifest, kind, &target.name));
+            println!("  [{}] {} → {}", kind.section(), target.name, target_path);
+            for key in EDITABLE_KEYS.iter().filter(|k| **k != "path") {
+                if let Some(value) = entry.get(key).and_then(|v| v.as_value()) {
+                    println!("      {} = {}", key, value.to_string().trim());
+                }
+            }
+        }
+
+        for target in discovered.iter().filter(|t| t.kind == kind) {
+            let target_path = target.path.as_deref().unwrap_or_default();
+            let shadowed = explicit.iter().any(|e| {
+                e.kind == kind
+                    && (kind == TargetKind::Lib
+                        || e.name == target.name
+                        || e.path.as_deref() == Some(target_path))
+            });
+            if shadowed {
+                continue;
+            }
+            let note = if auto_discovery_enabled(&manifest, kind) {
+                "auto-discovered".to_string()
+            } else {
+                format!("not built: {} = false", kind.auto_key())
+            };
+            println!(
+                "  [{}] {} → {} ({})",
+                kind.section(),
+                target.name,
+                target_path,
+                note
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn set(
+    path: &Path,
+    kind: TargetKind,
+    name: &str,
+    key: &str,
+    value: &str,
+    dry_run: bool,
+    quiet: bool,
+) -> Result<()> {
+    if !EDITABLE_KEYS.contains(&key) {
+        anyhow::bail!(
+            "Unsupported target key '{}'. Supported keys: {}",
+            key,
+            EDITABLE_KEYS.join(", ")
+        );
+    }
+
+    let mut manifest = Manifest::load(path)?;
+
+    let item_value = match key {
+        "harness" | "doc" => {
+            let b: bool = value
+                .parse()
+                .with_context(|| format!("'{}' must be true or false", key))?;
+            toml_edit::value(b)
+        }
+        "crate-type" | "required-features" => {
+            let items: Vec<&str> = value
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .collect();
+            if key == "crate-type" {
+                if !matches!(kind, TargetKind::Lib | TargetKind::Example) {
+                    anyhow::bail!("'crate-type' can only be set on lib and example targets");
+                }
+                for crate_type in &items {
+                    if !CRATE_TYPES.contains(crate_type) {
+                        anyhow::bail!(
+                            "Invalid crate type '{}'. Valid types: {}",
+                            crate_type,
+                            CRATE_TYPES.join(", ")
+                        );
+                    }
+                }
+            } else {
+                let graph = FeatureGraph::from_manifest(&manifest);
+                for feature in &items {
+                    if !graph.has_feature(feature) {
+                        print_warning(&format!(
+                            "Feature '{}' is not defined in [features]",
+                            feature
+                        ));
+                    }
+                }
+            }
+            let mut arr = Array::new();
+            for item in items {
+                arr.push(item);
+            }
+            Item::Value(Value::Array(arr))
+        }
+        _ => toml_edit::value(value),
+    };
+
+    let declared = explicit_targets(&manifest)
+        .iter()
+        .any(|t| t.kind == kind && (kind == TargetKind::Lib || t.name == name));
+    if !declared {
+        // Auto-discovered targets get an explicit entry carrying just their name
+        let discovered = discovered_targets(&manifest)
+            .iter()
+            .any(|t| t.kind == kind && t.name == name);
+        if !discovered {
+            anyhow::bail!("{} target '{}' not found", kind.section(), name);
+        }
+        if !dry_run {
+            let mut entry = Table::new();
+            entry.insert("name", toml_edit::value(name));
+            push_entry(&mut manifest, kind, entry)?;
+        }
+    }
+
+    if !dry_run {
+        let entry = entry_mut(&mut manifest, kind, name)
+            .with_context(|| format!("{} target '{}' not found", kind.section(), name))?;
+        entry.insert(key, item_value);
+        tidy_inline_entry(&mut manifest, kind, name);
+        manifest.save()?;
+    }
+
+    print_info(
+        &format!("[{}] {}: {} = {}", kind.section(), name, key, value),
+        quiet,
+    );
+    if !dry_run {
+        print_success("Targets updated", quiet);
+    } else {
+        print_info("(dry run - no changes made)", false);
+    }
+
+    Ok(())
+}
+
+fn unset(
+    path: &Path,
+    kind: TargetKind,
+    name: &str,
+    key: &str,
+    dry_run: bool,
+    quiet: bool,
+) -> Result<()> {
+    let mut manifest = Manifest::load(path)?;
+    let entry = entry_mut(&mut manifest, kind, name)
+        .with_context(|| format!("{} target '{}' not found", kind.section(), name))?;
+
+    if entry.contains_key(key) {
+        if !dry_run {
+            entry.remove(key);
+            tidy_inline_entry(&mut manifest, kind, name);
+            manifest.save()?;
+        }
+        print_success(
+            &format!("[{}] {}: '{}' removed", kind.section(), name, key),
+            quiet,
+        );
+    } else {
+        print_info(
+            &format!("[{}] {}: '{}' not set", kind.section(), name, key),
+            quiet,
+        );
+    }
+
+    Ok(())
+}
+
+/// Appends a target entry, keeping whichever array style the manifest already uses.
+fn push_entry(manifest: &mut Manifest, kind: TargetKind, entry: Table) -> Result<()> {
+    let section = kind.section();
+    if kind == TargetKind::Lib {
+        manifest.doc.insert(section, Item::Table(entry));
+        return Ok(());
+    }
+
+    match manifest
+        .doc
+        .entry(section)
+        .or_insert(Item::ArrayOfTables(ArrayOfTables::new()))
+    {
+        Item::ArrayOfTables(tables) => tables.push(entry),
+        Item::Value(Value::Array(array)) => {
+            let mut inline = InlineTable::new();
+            for (k, v) in entry.iter() {
+                if let Some(v) = v.as_value() {
+                    inline.insert(k, v.clone());
+                }
+            }
+            inline.fmt();
+            // Line up with the existing elements of a multi-line array
+            let mut value = Value::InlineTable(inline);
+            if let Some(prefix) = array
+                .iter()
+                .last()
+                .and_then(|v| v.decor().prefix())
+                .and_then(|p| p.as_str())
+            {
+                value.decor_mut().set_prefix(prefix.to_string());
+            }
+            array.push_formatted(value);
+        }
+        _ => anyhow::bail!("{} is not an array of tables", section),
+    }
+    Ok(())
+}
+
+fn entry_mut<'a>(
+    manifest: &'a mut Manifest,
+    kind: TargetKind,
+    name: &str,
+) -> Option<&'a mut dyn TableLike> {
+    match manifest.doc.get_mut(kind.section())? {
+        item if kind == TargetKind::Lib => item.as_table_like_mut(),
+        Item::ArrayOfTables(tables) => tables
+            .iter_mut()
+            .find(|t| t.get("name").and_then(|n| n.as_str()) == Some(name))
+            .map(|t| t as &mut dyn TableLike),
+        Item::Value(Value::Array(array)) => array
+            .iter_mut()
+            .filter_map(|v| v.as_inline_table_mut())
+            .find(|t| t.get("name").and_then(|n| n.as_str()) == Some(name))
+            .map(|t| t as &mut dyn TableLike),
+        _ => None,
+    }
+}
+
+/// Re-formats an edited `bin = [{ ... }]` style entry so inserted keys are spaced
+/// like the rest of it.
+fn tidy_inline_entry(manifest: &mut Manifest, kind: TargetKind, name: &str) {
+    if let Some(Item::Value(Value::Array(array))) = manifest.doc.get_mut(kind.section()) {
+        for table in array.iter_mut().filter_map(|v| v.as_inline_table_mut()) {
+            if table.get("name").and_then(|n| n.as_str()) == Some(name) {
+                table.fmt();
+            }
+        }
+    }
+}
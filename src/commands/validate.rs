@@ -1,154 +1,1106 @@
 // src/commands/validate.rs
+use super::categories::{is_valid_category, suggest_category};
+use super::keywords::{check_keyword, normalize_keyword};
+use super::profile::{check_profile_value, is_profile_key};
+use super::version::replace_str;
+use crate::build_targets::{
+    auto_discovery_enabled, default_target_path, default_target_paths, discovered_targets,
+    explicit_targets, target_entries, TargetKind,
+};
+use crate::feature_graph::{FeatureGraph, FeatureValue};
+use crate::findings::{
+    check_rule, key_path, to_json, to_junit, to_sarif, Findings, Level, Severity,
+};
 use crate::manifest::Manifest;
+use crate::msrv::{self, edition_requirement, RustVersion, EDITIONS};
+use crate::patterns::{matches_any, walk_files, Pattern};
+use crate::schema;
+use crate::semver::{Version, VersionReq};
+use crate::spdx;
+use crate::urls;
 use crate::utils::{print_error, print_success, print_warning};
-use anyhow::Result;
+use crate::workspace::{dependency_tables, Workspace};
+use crate::{RuleOptions, ValidateFormat};
+use anyhow::{bail, Result};
 use colored::*;
+use std::collections::HashMap;
 use std::path::Path;
 
-pub fn handle(path: &Path, strict: bool) -> Result<()> {
-    let manifest = Manifest::load(path)?;
-    let mut errors = Vec::new();
-    let mut warnings = Vec::new();
+pub fn handle(
+    path: &Path,
+    strict: bool,
+    format: ValidateFormat,
+    rules: RuleOptions,
+    fix: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let mut manifest = Manifest::load(path)?;
+    let levels = rule_levels(&manifest, rules)?;
+    let mut findings = Findings::default();
+    let text = matches!(format, ValidateFormat::Text);
 
-    println!("{}", "Validating Cargo.toml...".bold());
-    println!();
+    if text {
+        println!("{}", "Validating Cargo.toml...".bold());
+        println!();
+    }
+
+    if fix {
+        // With --dry-run the fixes are only reported and the file on disk is
+        // what gets validated.
+        let mut fixed = Manifest {
+            doc: manifest.doc.clone(),
+            path: manifest.path.clone(),
+        };
+        let fixes = apply_fixes(&mut fixed)?;
+        if !fixes.is_empty() && !dry_run {
+            fixed.save()?;
+            manifest = fixed;
+        }
+        // Keep machine-readable output on stdout clean
+        let report = |msg: &str| {
+            if text {
+                println!("{}", msg);
+            } else {
+                eprintln!("{}", msg);
+            }
+        };
+        for change in &fixes {
+            report(&format!("{} {}", "fixed:".green().bold(), change));
+        }
+        if fixes.is_empty() {
+            report("Nothing to fix");
+        } else if dry_run {
+            report("(dry run - no changes made)");
+        }
+        if text {
+            println!();
+        }
+    }
 
     // Check required fields
     if let Some(package) = manifest.package() {
-        check_required_field(package, "name", &mut errors);
-        check_required_field(package, "version", &mut errors);
+        check_required_field(package, "name", &mut findings);
+        check_required_field(package, "version", &mut findings);
+
+        // Publishing checks run with --strict, or when their rule is set to
+        // warn or deny explicitly.
+        let enabled =
+            |rule: &str| strict || levels.get(rule).is_some_and(|level| *level != Level::Allow);
+
+        if enabled("missing-field") {
+            check_required_field(package, "description", &mut findings);
+            check_required_field(package, "license", &mut findings);
+        }
 
-        if strict {
-            // Additional checks for publishing
-            check_required_field(package, "description", &mut errors);
-            check_required_field(package, "license", &mut errors);
+        // Recommended fields
+        for (field, rule) in [
+            ("repository", "missing-repository"),
+            ("readme", "missing-readme"),
+            ("keywords", "missing-keywords"),
+            ("categories", "missing-categories"),
+        ] {
+            if enabled(rule) && !package.contains_key(field) {
+                findings.warning(
+                    rule,
+                    &["package", field],
+                    format!("Missing '{}' field (recommended for crates.io)", field),
+                );
+            }
+        }
 
-            // Recommended fields
-            if !package.contains_key("repository") {
-                warnings.push("Missing 'repository' field (recommended for crates.io)".to_string());
+        // Check description length
+        if let Some(desc) = package.get("description").and_then(|v| v.as_str()) {
+            let key = ["package", "description"];
+            if enabled("description-too-long") && desc.len() > 160 {
+                findings.warning(
+                    "description-too-long",
+                    &key,
+                    "Description exceeds 160 characters (crates.io will truncate)",
+                );
             }
-            if !package.contains_key("readme") {
-                warnings.push("Missing 'readme' field (recommended for crates.io)".to_string());
+            if enabled("description-short") && desc.len() < 10 {
+                findings.warning(
+                    "description-short",
+                    &key,
+                    "Description is very short (consider expanding)",
+                );
             }
-            if !package.contains_key("keywords") {
-                warnings.push("Missing 'keywords' field (recommended for crates.io)".to_string());
+        }
+
+        // --fix leaves keywords it cannot normalise, so report them
+        if enabled("keywords") || fix {
+            check_keywords(package, &mut findings);
+        }
+        if enabled("categories") {
+            check_categories(package, &mut findings);
+        }
+
+        if let Some(license) = package.get("license").and_then(|v| v.as_str()) {
+            let report = spdx::check(license);
+            let key = ["package", "license"];
+            // crates.io rejects invalid expressions, so they only fail strict mode.
+            let severity = if strict {
+                Severity::Error
+            } else {
+                Severity::Warning
+            };
+            for error in report.errors {
+                findings.push(severity, "license", &key, error);
             }
-            if !package.contains_key("categories") {
-                warnings.push("Missing 'categories' field (recommended for crates.io)".to_string());
+            for warning in report.warnings {
+                findings.warning("license", &key, warning);
             }
+        }
 
-            // Check description length
-            if let Some(desc) = package.get("description").and_then(|v| v.as_str()) {
-                if desc.len() > 160 {
-                    warnings.push(
-                        "Description exceeds 160 characters (crates.io will truncate)".to_string(),
-                    );
-                }
-                if desc.len() < 10 {
-                    warnings.push("Description is very short (consider expanding)".to_string());
+        check_urls(package, &mut findings);
+
+        // Check version format
+        if let Some(version) = package.get("version").and_then(|v| v.as_str()) {
+            if let Err(e) = Version::parse(version) {
+                findings.error(
+                    "version",
+                    &["package", "version"],
+                    format!("Invalid version '{}': {}", version, e),
+                );
+            }
+        }
+    } else {
+        findings.error("missing-package", &[], "Missing [package] section");
+    }
+
+    check_package_name(&manifest, &mut findings);
+    check_rust_version(&manifest, &mut findings);
+    check_unknown_keys(&manifest, &mut findings);
+    check_files(&manifest, &mut findings);
+    check_dependencies(&manifest, &mut findings);
+    check_features(&manifest, &mut findings);
+    check_targets(&manifest, &mut findings);
+    check_profiles(&manifest, &mut findings);
+
+    findings.apply_levels(&levels);
+    // The document keeps its formatting, so this matches the file on disk
+    findings.locate(&manifest.doc.to_string());
+    let errors = findings.errors().count();
+
+    let manifest_name = path.to_string_lossy().replace('\\', "/");
+    match format {
+        ValidateFormat::Text => print_text(&findings),
+        ValidateFormat::Json => println!("{}", to_json(&findings, &manifest_name)),
+        ValidateFormat::Sarif => println!("{}", to_sarif(&findings, &manifest_name)),
+        ValidateFormat::Junit => print!("{}", to_junit(&findings, &manifest_name)),
+    }
+
+    if errors > 0 {
+        bail!("Validation failed with {} error(s)", errors);
+    }
+    Ok(())
+}
+
+/// Rule levels from the `validate` table of cargoe's config, overridden by the
+/// command line.
+fn rule_levels(manifest: &Manifest, rules: RuleOptions) -> Result<HashMap<String, Level>> {
+    let mut levels = HashMap::new();
+
+    if let Some(config) = manifest.cargoe_config().and_then(|c| c.get("validate")) {
+        let Some(config) = config.as_table_like() else {
+            bail!("cargoe config 'validate' must be a table of rule = \"allow\"|\"warn\"|\"deny\"");
+        };
+        for (rule, level) in config.iter() {
+            check_rule(rule).map_err(anyhow::Error::msg)?;
+            let Some(level) = level.as_str().and_then(Level::parse) else {
+                bail!(
+                    "Rule '{}' must be set to \"allow\", \"warn\" or \"deny\"",
+                    rule
+                );
+            };
+            levels.insert(rule.to_string(), level);
+        }
+    }
+
+    for (rules, level) in [
+        (rules.allow, Level::Allow),
+        (rules.warn, Level::Warn),
+        (rules.deny, Level::Deny),
+    ] {
+        for rule in rules {
+            check_rule(&rule).map_err(anyhow::Error::msg)?;
+            levels.insert(rule, level);
+        }
+    }
+    Ok(levels)
+}
+
+fn print_text(findings: &Findings) {
+    println!("{}", "Results:".bold());
+    println!();
+
+    if findings.items.is_empty() {
+        print_success("All checks passed!", false);
+        return;
+    }
+
+    if findings.errors().next().is_some() {
+        println!("{}", "Errors:".red().bold());
+        for err in findings.errors() {
+            print_error(&format!(
+                "{} {}",
+                err.message,
+                format!("[{}]", err.rule).dimmed()
+            ));
+        }
+        println!();
+    }
+
+    if findings.warnings().next().is_some() {
+        println!("{}", "Warnings:".yellow().bold());
+        for warn in findings.warnings() {
+            print_warning(&format!(
+                "{} {}",
+                warn.message,
+                format!("[{}]", warn.rule).dimmed()
+            ));
+        }
+        println!();
+    }
+}
+
+/// Applies the fixes that cannot change what the package means and describes
+/// each one.
+fn apply_fixes(manifest: &mut Manifest) -> Result<Vec<String>> {
+    let root = manifest
+        .path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .to_path_buf();
+    let name = manifest.get_package_name();
+    let package = manifest.package_mut()?;
+    let mut fixes = Vec::new();
+
+    if let Some(license) = package.get("license").and_then(|v| v.as_str()) {
+        if let Some(fixed) = spdx::fix(license) {
+            fixes.push(format!("license '{}' -> '{}'", license, fixed));
+            replace_str(package.get_mut("license"), &fixed);
+        }
+    }
+
+    if let Some(repository) = package.get("repository").and_then(|v| v.as_str()) {
+        let clean = urls::check("repository", repository).is_ok_and(|w| w.is_empty());
+        if !clean {
+            if let Ok(fixed) = urls::normalize_repository(repository) {
+                if urls::check("repository", &fixed).is_ok_and(|w| w.is_empty()) {
+                    fixes.push(format!("repository '{}' -> '{}'", repository, fixed));
+                    replace_str(package.get_mut("repository"), &fixed);
                 }
             }
+        }
+    }
 
-            // Check keywords count and length
-            if let Some(keywords) = package.get("keywords").and_then(|v| v.as_array()) {
-                if keywords.len() > 5 {
-                    errors.push("Too many keywords (max 5 for crates.io)".to_string());
+    if let Some(keywords) = package.get_mut("keywords").and_then(|v| v.as_array_mut()) {
+        let mut seen: Vec<String> = Vec::new();
+        let mut i = 0;
+        while i < keywords.len() {
+            let Some(keyword) = keywords.get(i).and_then(|v| v.as_str()).map(str::to_string) else {
+                i += 1;
+                continue;
+            };
+            match normalize_keyword(&keyword) {
+                Some(fixed) if seen.contains(&fixed) => {
+                    fixes.push(format!("removed duplicate keyword '{}'", keyword));
+                    keywords.remove(i);
+                    continue;
                 }
-                for kw in keywords.iter() {
-                    if let Some(s) = kw.as_str() {
-                        if s.len() > 20 {
-                            errors.push(format!("Keyword '{}' exceeds 20 characters", s));
-                        }
+                Some(fixed) => {
+                    if fixed != keyword {
+                        fixes.push(format!("keyword '{}' -> '{}'", keyword, fixed));
+                        replace_array_str(keywords, i, &fixed);
                     }
+                    seen.push(fixed);
                 }
+                // Left for the keywords check to report
+                None => {}
             }
+            i += 1;
+        }
+    }
 
-            // Check categories count
-            if let Some(categories) = package.get("categories").and_then(|v| v.as_array()) {
-                if categories.len() > 5 {
-                    errors.push("Too many categories (max 5 for crates.io)".to_string());
-                }
+    if let Some(categories) = package.get_mut("categories").and_then(|v| v.as_array_mut()) {
+        for i in 0..categories.len() {
+            let Some(category) = categories.get(i).and_then(|v| v.as_str()) else {
+                continue;
+            };
+            if is_valid_category(category) {
+                continue;
+            }
+            if let Some(fixed) = suggest_category(category) {
+                fixes.push(format!("category '{}' -> '{}'", category, fixed));
+                replace_array_str(categories, i, fixed);
             }
+        }
+    }
 
-            // Check license format
-            if let Some(license) = package.get("license").and_then(|v| v.as_str()) {
-                if !is_valid_spdx_license(license) {
-                    warnings.push(format!(
-                        "License '{}' may not be a valid SPDX expression",
-                        license
-                    ));
-                }
+    if !package.contains_key("documentation") && publishes_to_crates_io(package) {
+        if let Some(name) = name {
+            let docs = format!("https://docs.rs/{}", name);
+            fixes.push(format!("documentation = '{}'", docs));
+            package.insert("documentation", toml_edit::value(docs));
+        }
+    }
+
+    if !package.contains_key("readme") {
+        let found = [
+            "README.md",
+            "README",
+            "README.txt",
+            "readme.md",
+            "Readme.md",
+        ]
+        .into_iter()
+        .find(|candidate| root.join(candidate).is_file());
+        if let Some(readme) = found {
+            fixes.push(format!("readme = '{}'", readme));
+            package.insert("readme", toml_edit::value(readme));
+        }
+    }
+
+    Ok(fixes)
+}
+
+/// docs.rs only builds crates released on crates.io: `publish` must be unset,
+/// `true` or a registry list naming `crates-io`.
+fn publishes_to_crates_io(package: &toml_edit::Table) -> bool {
+    match package.get("publish") {
+        None => true,
+        Some(publish) => {
+            publish.as_bool().unwrap_or(false)
+                || publish
+                    .as_array()
+                    .is_some_and(|r| r.iter().any(|r| r.as_str() == Some("crates-io")))
+        }
+    }
+}
+
+fn replace_array_str(array: &mut toml_edit::Array, index: usize, new: &str) {
+    if let Some(value) = array.get_mut(index) {
+        let decor = value.decor().clone();
+        *value = new.into();
+        *value.decor_mut() = decor;
+    }
+}
+
+fn check_required_field(package: &toml_edit::Table, field: &str, findings: &mut Findings) {
+    if !package.contains_key(field) {
+        findings.error(
+            "missing-field",
+            &["package", field],
+            format!("Missing required field: '{}'", field),
+        );
+    }
+}
+
+/// Names crates.io or Cargo will not accept for a package.
+const RESERVED_NAMES: &[&str] = &[
+    "alloc",
+    "core",
+    "proc_macro",
+    "proc-macro",
+    "std",
+    "test",
+    // Cargo's own directories under target/
+    "build",
+    "deps",
+    "examples",
+    "incremental",
+    // Windows device names
+    "con",
+    "prn",
+    "aux",
+    "nul",
+    "com1",
+    "com2",
+    "com3",
+    "com4",
+    "com5",
+    "com6",
+    "com7",
+    "com8",
+    "com9",
+    "lpt1",
+    "lpt2",
+    "lpt3",
+    "lpt4",
+    "lpt5",
+    "lpt6",
+    "lpt7",
+    "lpt8",
+    "lpt9",
+];
+
+/// crates.io's rules for `package.name`, plus names in the same workspace that
+/// only differ by `-` and `_` (crates.io treats them as the same crate).
+fn check_package_name(manifest: &Manifest, findings: &mut Findings) {
+    let Some(name) = manifest.get_package_name() else {
+        return;
+    };
+    let key = ["package", "name"];
+
+    let problem = if name.is_empty() {
+        Some("must not be empty".to_string())
+    } else if name.len() > 64 {
+        Some(format!("is {} characters long (max 64)", name.len()))
+    } else if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        Some("must start with an ASCII letter".to_string())
+    } else if let Some(c) = name
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && *c != '-' && *c != '_')
+    {
+        Some(format!(
+            "contains '{}'; only ASCII letters, digits, '-' and '_' are allowed",
+            c
+        ))
+    } else if RESERVED_NAMES.contains(&name.to_ascii_lowercase().as_str()) {
+        Some("is a reserved name".to_string())
+    } else {
+        None
+    };
+    if let Some(problem) = problem {
+        findings.error(
+            "package-name",
+            &key,
+            format!("Package name '{}' {}", name, problem),
+        );
+    }
+
+    if name.contains('-') && name.contains('_') {
+        findings.warning(
+            "package-name-style",
+            &key,
+            format!("Package name '{}' mixes '-' and '_'", name),
+        );
+    }
+
+    let normalize = |n: &str| n.to_ascii_lowercase().replace('_', "-");
+    // A broken sibling manifest is not this package's problem
+    if let Ok(Some(workspace)) = Workspace::discover(&manifest.path) {
+        for other in &workspace.manifests {
+            let Some(other_name) = other.get_package_name() else {
+                continue;
+            };
+            if other_name != name && normalize(&other_name) == normalize(&name) {
+                findings.warning(
+                    "package-name-collision",
+                    &key,
+                    format!(
+                        "Package name '{}' collides with '{}' at '{}' (crates.io treats '-' and '_' alike)",
+                        name,
+                        other_name,
+                        other.path.display()
+                    ),
+                );
             }
         }
+    }
+}
 
-        // Check version format
-        if let Some(version) = package.get("version").and_then(|v| v.as_str()) {
-            if !is_valid_semver(version) {
-                errors.push(format!("Invalid version format: '{}'", version));
+/// Cargo ignores keys it does not know (with a warning at build time), so typos
+/// like `licence` silently drop the field.
+fn check_unknown_keys(manifest: &Manifest, findings: &mut Findings) {
+    for unknown in schema::unknown_keys(&manifest.doc) {
+        let name = unknown.key.last().map(String::as_str).unwrap_or_default();
+        let message = match unknown.suggestion {
+            Some(s) => format!(
+                "Unknown key '{}' in {}; did you mean '{}'?",
+                name, unknown.table, s
+            ),
+            None => format!("Unknown key '{}' in {}", name, unknown.table),
+        };
+        let key: Vec<&str> = unknown.key.iter().map(String::as_str).collect();
+        findings.warning("unknown-key", &key, message);
+    }
+}
+
+/// `edition` and `rust-version` must be valid, and `rust-version` must be at
+/// least what the manifest itself needs.
+fn check_rust_version(manifest: &Manifest, findings: &mut Findings) {
+    let Some(package) = manifest.package() else {
+        return;
+    };
+
+    if let Some(edition) = package.get("edition").and_then(|e| e.as_str()) {
+        if edition_requirement(edition).is_none() {
+            findings.error(
+                "edition",
+                &["package", "edition"],
+                format!(
+                    "Unknown edition '{}' (expected one of {})",
+                    edition,
+                    EDITIONS.join(", ")
+                ),
+            );
+        }
+    }
+
+    let Some(rust_version) = package.get("rust-version").and_then(|v| v.as_str()) else {
+        return;
+    };
+    let rust_version = match RustVersion::parse(rust_version) {
+        Ok(version) => version,
+        Err(e) => {
+            findings.error(
+                "rust-version",
+                &["package", "rust-version"],
+                format!("Invalid rust-version '{}': {}", rust_version, e),
+            );
+            return;
+        }
+    };
+
+    for requirement in msrv::requirements(manifest) {
+        if requirement.version > rust_version {
+            let key: Vec<&str> = requirement.key.iter().map(String::as_str).collect();
+            findings.error(
+                "msrv",
+                &key,
+                format!(
+                    "rust-version {} is lower than {} required by {}",
+                    rust_version, requirement.version, requirement.reason
+                ),
+            );
+        }
+    }
+}
+
+/// crates.io's rules for `keywords`, which it enforces on publish.
+fn check_keywords(package: &toml_edit::Table, findings: &mut Findings) {
+    let keywords = string_array(package, "keywords");
+    if keywords.len() > 5 {
+        findings.error(
+            "keywords",
+            &["package", "keywords"],
+            "Too many keywords (max 5 for crates.io)",
+        );
+    }
+    for (i, keyword) in keywords.iter().enumerate() {
+        let key = ["package", "keywords", &i.to_string()];
+        if let Err(e) = check_keyword(keyword) {
+            findings.error("keywords", &key, e);
+        }
+        if keywords[..i]
+            .iter()
+            .any(|k| k.eq_ignore_ascii_case(keyword))
+        {
+            findings.error("keywords", &key, format!("Duplicate keyword '{}'", keyword));
+        }
+    }
+}
+
+/// crates.io's rules for `categories`, which it enforces on publish.
+fn check_categories(package: &toml_edit::Table, findings: &mut Findings) {
+    let categories = string_array(package, "categories");
+    if categories.len() > 5 {
+        findings.error(
+            "categories",
+            &["package", "categories"],
+            "Too many categories (max 5 for crates.io)",
+        );
+    }
+    for (i, category) in categories.iter().enumerate() {
+        let key = ["package", "categories", &i.to_string()];
+        if !is_valid_category(category) {
+            findings.error(
+                "categories",
+                &key,
+                match suggest_category(category) {
+                    Some(s) => format!("Unknown category '{}'; did you mean '{}'?", category, s),
+                    None => format!(
+                        "Unknown category '{}' (see 'cargoe categories valid')",
+                        category
+                    ),
+                },
+            );
+        }
+        if categories[..i].contains(category) {
+            findings.error(
+                "categories",
+                &key,
+                format!("Duplicate category '{}'", category),
+            );
+        }
+    }
+}
+
+fn string_array<'a>(package: &'a toml_edit::Table, key: &str) -> Vec<&'a str> {
+    package
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// `repository`, `homepage` and `documentation` must be plain https links.
+fn check_urls(package: &toml_edit::Table, findings: &mut Findings) {
+    let get = |key| package.get(key).and_then(|v| v.as_str());
+    for field in ["repository", "homepage", "documentation"] {
+        let Some(value) = get(field) else { continue };
+        match urls::check(field, value) {
+            Ok(warnings) => {
+                for warning in warnings {
+                    findings.warning("url", &["package", field], warning);
+                }
             }
+            Err(e) => findings.error("url", &["package", field], e),
         }
-    } else {
-        errors.push("Missing [package] section".to_string());
     }
 
-    // Print results
-    println!("{}", "Results:".bold());
-    println!();
+    if let (Some(homepage), Some(repository)) = (get("homepage"), get("repository")) {
+        if urls::same_url(homepage, repository) {
+            findings.warning(
+                "homepage-is-repository",
+                &["package", "homepage"],
+                "'homepage' duplicates 'repository'; omit it unless the project has its own site",
+            );
+        }
+    }
+}
 
-    if errors.is_empty() && warnings.is_empty() {
-        print_success("All checks passed!", false);
-        Ok(())
-    } else {
-        if !errors.is_empty() {
-            println!("{}", "Errors:".red().bold());
-            for err in &errors {
-                print_error(err);
+/// Paths named in `[package]` must exist relative to the manifest, and `include`
+/// entries must match something.
+fn check_files(manifest: &Manifest, findings: &mut Findings) {
+    let Some(package) = manifest.package() else {
+        return;
+    };
+    let root = manifest.path.parent().unwrap_or(Path::new("."));
+    let field = |key: &str| package.get(key).and_then(|v| v.as_str());
+
+    for key in ["readme", "license-file", "build"] {
+        if let Some(file) = field(key) {
+            if !root.join(file).is_file() {
+                findings.error(
+                    "missing-file",
+                    &["package", key],
+                    format!("'{}' file '{}' does not exist", key, file),
+                );
             }
-            println!();
         }
+    }
+    if package.get("build").and_then(|v| v.as_bool()) == Some(true)
+        && !root.join("build.rs").is_file()
+    {
+        findings.error(
+            "missing-file",
+            &["package", "build"],
+            "'build = true' but build.rs does not exist",
+        );
+    }
+    if let Some(workspace) = field("workspace") {
+        if !root.join(workspace).join("Cargo.toml").is_file() {
+            findings.error(
+                "missing-file",
+                &["package", "workspace"],
+                format!(
+                    "'workspace' path '{}' does not contain a Cargo.toml",
+                    workspace
+                ),
+            );
+        }
+    }
+
+    if field("license").is_some() && field("license-file").is_some() {
+        findings.warning(
+            "license-and-license-file",
+            &["package", "license-file"],
+            "Both 'license' and 'license-file' are set; only one is needed",
+        );
+    }
 
-        if !warnings.is_empty() {
-            println!("{}", "Warnings:".yellow().bold());
-            for warn in &warnings {
-                print_warning(warn);
+    // Patterns paired with their index in the array
+    let mut patterns = |key: &str| -> (Vec<usize>, Vec<Pattern>) {
+        let Some(array) = package.get(key).and_then(|v| v.as_array()) else {
+            return Default::default();
+        };
+        array
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| v.as_str().map(|p| (i, p)))
+            .filter_map(|(i, p)| match Pattern::new(p) {
+                Ok(pattern) => Some((i, pattern)),
+                Err(e) => {
+                    findings.error(
+                        "invalid-pattern",
+                        &["package", key, &i.to_string()],
+                        format!("Invalid '{}' entry: {}", key, e),
+                    );
+                    None
+                }
+            })
+            .unzip()
+    };
+    let (include_index, include) = patterns("include");
+    let (exclude_index, exclude) = patterns("exclude");
+
+    if !include.is_empty() {
+        let files = walk_files(root);
+        for (i, pattern) in include_index.iter().zip(&include) {
+            if !pattern.negated && !files.iter().any(|f| pattern.matches(f)) {
+                findings.error(
+                    "unmatched-include",
+                    &["package", "include", &i.to_string()],
+                    format!(
+                        "'include' entry '{}' does not match any file",
+                        pattern.source
+                    ),
+                );
             }
-            println!();
         }
+    }
 
-        if !errors.is_empty() {
-            anyhow::bail!("Validation failed with {} error(s)", errors.len());
+    // Cargo packages the readme even when `exclude` matches it, so such a
+    // pattern only misleads. `exclude` is ignored altogether with `include`.
+    if let Some(readme) = field("readme").filter(|_| include.is_empty()) {
+        let readme = readme.trim_start_matches("./").replace('\\', "/");
+        if let Some(pattern) = matches_any(&exclude, &readme) {
+            let i = exclude
+                .iter()
+                .position(|p| std::ptr::eq(p, pattern))
+                .map(|i| exclude_index[i])
+                .unwrap_or_default();
+            findings.warning(
+                "readme-excluded",
+                &["package", "exclude", &i.to_string()],
+                format!(
+                    "'exclude' entry '{}' matches readme '{}', which cargo packages anyway",
+                    pattern.source, readme
+                ),
+            );
         }
+    }
+}
+
+/// Version requirements must parse, and a `path` dependency's requirement must
+/// accept the version of the crate it points at.
+fn check_dependencies(manifest: &Manifest, findings: &mut Findings) {
+    let root = manifest.path.parent().unwrap_or(Path::new("."));
+
+    for (table_key, table) in dependency_tables(&manifest.doc) {
+        let section = key_path(&table_key);
+        for (name, dep) in table.iter() {
+            let req = dep
+                .as_str()
+                .or_else(|| dep.get("version").and_then(|v| v.as_str()));
+            let Some(req) = req else { continue };
+            let mut key = table_key.clone();
+            key.push(name);
+
+            let parsed = match VersionReq::parse(req) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    findings.error(
+                        "invalid-requirement",
+                        &key,
+                        format!(
+                            "[{}] '{}': invalid version requirement '{}': {}",
+                            section, name, req, e
+                        ),
+                    );
+                    continue;
+                }
+            };
+            if parsed.comparators.iter().any(|c| !c.build.is_empty()) {
+                findings.warning(
+                    "requirement-build-metadata",
+                    &key,
+                    format!(
+                        "[{}] '{}': build metadata in '{}' is ignored by Cargo",
+                        section, name, req
+                    ),
+                );
+            }
 
-        Ok(())
+            let Some(dep_path) = dep.get("path").and_then(|p| p.as_str()) else {
+                continue;
+            };
+            let Ok(local) = Manifest::load(&root.join(dep_path).join("Cargo.toml")) else {
+                continue;
+            };
+            let Some(Ok(version)) = local.get_package_version().map(|v| Version::parse(&v)) else {
+                continue;
+            };
+            if !parsed.matches(&version) {
+                findings.error(
+                    "path-dependency-version",
+                    &key,
+                    format!(
+                        "[{}] '{}': requirement '{}' does not match version {} at '{}'",
+                        section, name, req, version, dep_path
+                    ),
+                );
+            }
+        }
     }
 }
 
-fn check_required_field(package: &toml_edit::Table, field: &str, errors: &mut Vec<String>) {
-    if !package.contains_key(field) {
-        errors.push(format!("Missing required field: '{}'", field));
+fn check_features(manifest: &Manifest, findings: &mut Findings) {
+    let graph = FeatureGraph::from_manifest(manifest);
+    if graph.is_empty() {
+        return;
+    }
+
+    let dep_syntax = graph.dep_syntax_deps();
+
+    for (name, entries) in &graph.features {
+        if graph.is_optional_dep(name) && !dep_syntax.contains(name) {
+            findings.error(
+                "feature-shadows-dependency",
+                &["features", name],
+                format!(
+                    "Feature '{}' has the same name as optional dependency '{}' (use 'dep:{}' to enable the dependency)",
+                    name, name, name
+                ),
+            );
+        }
+
+        for (i, entry) in entries.iter().enumerate() {
+            let problem = match FeatureValue::parse(entry) {
+                FeatureValue::Feature(f) => {
+                    if graph.has_feature(&f) {
+                        None
+                    } else if graph.is_optional_dep(&f) {
+                        Some(format!(
+                            "'{}' is an optional dependency without an implicit feature, use 'dep:{}'",
+                            f, f
+                        ))
+                    } else if graph.deps.contains_key(&f) {
+                        Some(format!("'{}' is not an optional dependency", f))
+                    } else {
+                        Some(format!("'{}' is neither a feature nor a dependency", f))
+                    }
+                }
+                FeatureValue::Dep(d) => {
+                    if !graph.deps.contains_key(&d) {
+                        Some(format!("'{}' is not a dependency", d))
+                    } else if !graph.is_optional_dep(&d) {
+                        Some(format!("'{}' is not an optional dependency", d))
+                    } else {
+                        None
+                    }
+                }
+                FeatureValue::DepFeature { dep, weak, .. } => {
+                    if !graph.deps.contains_key(&dep) {
+                        Some(format!("'{}' is not a dependency", dep))
+                    } else if weak && !graph.is_optional_dep(&dep) {
+                        Some(format!(
+                            "'{}?' is used but '{}' is not an optional dependency",
+                            dep, dep
+                        ))
+                    } else {
+                        None
+                    }
+                }
+            };
+
+            if let Some(problem) = problem {
+                findings.error(
+                    "invalid-feature",
+                    &["features", name, &i.to_string()],
+                    format!("Feature '{}' entry '{}': {}", name, entry, problem),
+                );
+            }
+        }
+    }
+
+    for cycle in graph.cycles() {
+        findings.error(
+            "feature-cycle",
+            &["features", &cycle[0]],
+            format!("Feature cycle: {}", cycle.join(" -> ")),
+        );
+    }
+
+    for (dep, &optional) in &graph.deps {
+        if !optional {
+            continue;
+        }
+        let referenced = graph.features.iter().any(|(_, entries)| {
+            entries.iter().any(|e| match FeatureValue::parse(e) {
+                FeatureValue::Feature(f) => &f == dep,
+                FeatureValue::Dep(d) => &d == dep,
+                FeatureValue::DepFeature { dep: d, .. } => &d == dep,
+            })
+        });
+        if !referenced {
+            findings.warning(
+                "unused-optional-dependency",
+                &["dependencies", dep],
+                format!(
+                    "Optional dependency '{}' is not enabled by any feature (only by its implicit feature)",
+                    dep
+                ),
+            );
+        }
     }
 }
 
-fn is_valid_semver(version: &str) -> bool {
-    let parts: Vec<&str> = version.split('.').collect();
-    if parts.len() != 3 {
-        return false;
+fn check_targets(manifest: &Manifest, findings: &mut Findings) {
+    let root = manifest.path.parent().unwrap_or(Path::new("."));
+    let explicit = explicit_targets(manifest);
+    let discovered = discovered_targets(manifest);
+    let graph = FeatureGraph::from_manifest(manifest);
+
+    for kind in TargetKind::ALL {
+        let section = kind.section();
+        let entries = target_entries(manifest, kind);
+        let declared: Vec<_> = explicit.iter().filter(|t| t.kind == kind).collect();
+
+        for (i, (target, entry)) in declared.iter().zip(entries).enumerate() {
+            let index = i.to_string();
+            let key: &[&str] = if kind == TargetKind::Lib {
+                &[section]
+            } else {
+                &[section, &index]
+            };
+
+            if target.name.is_empty() {
+                findings.error(
+                    "target-name",
+                    key,
+                    format!("[{}] entry #{} has no 'name'", section, i + 1),
+                );
+            } else if declared[..i].iter().any(|t| t.name == target.name) {
+                findings.error(
+                    "target-name",
+                    key,
+                    format!("Duplicate [{}] target name '{}'", section, target.name),
+                );
+            }
+
+            let target_path = match &target.path {
+                Some(p) => p.clone(),
+                None => default_target_path(manifest, kind, &target.name),
+            };
+            if !root.join(&target_path).is_file() {
+                let message = if target.path.is_some() {
+                    format!(
+                        "[{}] '{}': path '{}' does not exist",
+                        section, target.name, target_path
+                    )
+                } else {
+                    let candidates = default_target_paths(manifest, kind, &target.name);
+                    format!(
+                        "[{}] '{}': path '{}' (default path) does not exist",
+                        section,
+                        target.name,
+                        candidates.join("' or '")
+                    )
+                };
+                findings.error("target-path", &[key, &["path"]].concat(), message);
+            }
+
+            if let Some(features) = entry.get("required-features").and_then(|f| f.as_array()) {
+                for feature in features.iter().filter_map(|f| f.as_str()) {
+                    if !graph.has_feature(feature) {
+                        findings.error(
+                            "target-required-features",
+                            &[key, &["required-features"]].concat(),
+                            format!(
+                                "[{}] '{}': required feature '{}' is not defined in [features]",
+                                section, target.name, feature
+                            ),
+                        );
+                    }
+                }
+            }
+
+            // An entry with nothing beyond name and path only restates what cargo infers
+            let redundant = auto_discovery_enabled(manifest, kind)
+                && entry.iter().all(|(k, _)| k == "name" || k == "path")
+                && discovered.iter().any(|d| {
+                    d.kind == kind
+                        && d.name == target.name
+                        && d.path.as_deref() == Some(target_path.as_str())
+                });
+            if redundant {
+                findings.warning(
+                    "redundant-target",
+                    key,
+                    format!(
+                        "[{}] '{}' duplicates the auto-discovered target at '{}'",
+                        section, target.name, target_path
+                    ),
+                );
+            }
+        }
+
+        if !auto_discovery_enabled(manifest, kind) {
+            for target in discovered.iter().filter(|d| d.kind == kind) {
+                let target_path = target.path.as_deref().unwrap_or_default();
+                let covered = declared.iter().any(|t| {
+                    t.name == target.name
+                        || t.path.as_deref() == Some(target_path)
+                        || default_target_path(manifest, kind, &t.name) == target_path
+                });
+                if !covered {
+                    findings.warning(
+                        "undiscovered-target",
+                        &["package", kind.auto_key()],
+                        format!(
+                            "'{}' is not built because {} = false",
+                            target_path,
+                            kind.auto_key()
+                        ),
+                    );
+                }
+            }
+        }
     }
-    parts.iter().all(|p| p.parse::<u32>().is_ok())
 }
 
-fn is_valid_spdx_license(license: &str) -> bool {
-    // Basic SPDX validation - common licenses
-    let common = [
-        "MIT",
-        "Apache-2.0",
-        "GPL-3.0",
-        "BSD-3-Clause",
-        "ISC",
-        "MPL-2.0",
-        "MIT OR Apache-2.0",
-        "MIT AND Apache-2.0",
-    ];
-    // common.iter().any(|&l| license == l) || license.contains(" OR ") || license.contains(" AND ")
-    common.contains(&license) || license.contains(" OR ") || license.contains(" AND ")
+fn check_profiles(manifest: &Manifest, findings: &mut Findings) {
+    let Some(profiles) = manifest.doc.get("profile").and_then(|p| p.as_table_like()) else {
+        return;
+    };
+
+    for (name, profile) in profiles.iter() {
+        let Some(profile) = profile.as_table_like() else {
+            findings.error(
+                "profile",
+                &["profile", name],
+                format!("[profile.{}] is not a table", name),
+            );
+            continue;
+        };
+
+        let builtin = matches!(name, "dev" | "release" | "test" | "bench");
+        if !builtin && !profile.contains_key("inherits") {
+            findings.error(
+                "profile",
+                &["profile", name],
+                format!(
+                    "Custom profile '{}' must set 'inherits' (e.g. inherits = \"release\")",
+                    name
+                ),
+            );
+        }
+
+        for (key, value) in profile.iter() {
+            // Overrides like `package` and `build-override` are tables
+            let Some(value) = value.as_value() else {
+                continue;
+            };
+            if !is_profile_key(key) {
+                continue;
+            }
+            if let Err(problem) = check_profile_value(key, value) {
+                findings.error(
+                    "profile",
+                    &["profile", name, key],
+                    format!(
+                        "[profile.{}] {} = {}: {}",
+                        name,
+                        key,
+                        value.to_string().trim(),
+                        problem
+                    ),
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,650 @@
+// src/commands/version.rs
+use super::changelog;
+use crate::git;
+use crate::manifest::Manifest;
+use crate::semver::{Op, Version, VersionReq};
+use crate::utils::{print_info, print_success, print_warning};
+use crate::workspace::{for_each_dependency_table_mut, path_points_to, Workspace};
+use crate::{BumpLevel, VersionCommands};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::collections::{BTreeSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn handle(path: &Path, cmd: VersionCommands, dry_run: bool, quiet: bool) -> Result<()> {
+    let manifest = Manifest::load(path)?;
+    let current = current_version(&manifest)?;
+
+    let (next, options) = match cmd {
+        VersionCommands::Bump {
+            level,
+            pre_id,
+            options,
+        } => (bump(&current, level, &pre_id)?, options),
+        VersionCommands::Sync { check } => return sync(&manifest, &current, check, dry_run, quiet),
+        VersionCommands::Set { version, options } => {
+            let next = Version::parse(&version)
+                .map_err(|e| anyhow::anyhow!("Invalid version '{}': {}", version, e))?;
+            if next < current {
+                print_warning(&format!(
+                    "{} is lower than the current version {}",
+                    next, current
+                ));
+            }
+            (next, options)
+        }
+    };
+
+    if next == current && next.build == current.build {
+        print_info(&format!("Version is already {}", current), quiet);
+        return Ok(());
+    }
+
+    print_info(&format!("{} → {}", current, next), quiet);
+
+    let workspace = if options.propagate || options.cascade {
+        let workspace = Workspace::discover(path)?;
+        if workspace.is_none() {
+            print_warning("Manifest is not part of a workspace; nothing to propagate");
+        }
+        workspace
+    } else {
+        None
+    };
+
+    // Prepare the other files first so a malformed CHANGELOG.md or a sync target
+    // that no longer matches stops the release before any manifest is written.
+    let mut files = Vec::new();
+    if options.changelog {
+        files.push(changelog::prepare_release(&manifest, &next, quiet)?);
+    }
+    for file in sync_files(&manifest, &next)? {
+        if file.changed() {
+            print_info(&format!("  {}", file.describe(&next)), quiet);
+            files.push((file.path, file.updated));
+        }
+    }
+
+    let manifests = match workspace {
+        Some(mut workspace) => {
+            let index = workspace
+                .find(path)
+                .context("manifest not found in workspace")?;
+            let changed = propagate(&mut workspace, index, &next, options.cascade, quiet)?;
+            workspace
+                .manifests
+                .into_iter()
+                .zip(changed)
+                .filter_map(|(manifest, changed)| changed.then_some(manifest))
+                .collect()
+        }
+        None => {
+            let mut manifest = Manifest::load(path)?;
+            set_version(&mut manifest, &next)?;
+            vec![manifest]
+        }
+    };
+
+    let release = if options.commit {
+        let tag = options
+            .tag
+            .then(|| changelog::tag_name(&manifest, &next.to_string()));
+        let mut paths: Vec<PathBuf> = manifests.iter().map(|m| m.path.clone()).collect();
+        paths.extend(files.iter().map(|(p, _)| p.clone()));
+        let repo = git::Repository::open(path)?;
+        // Cargo.lock records workspace member versions; leaving it stale would
+        // break `--locked` builds of the tagged commit.
+        let lockfile = match find_lockfile(path) {
+            Some(lockfile) if repo.is_tracked(&lockfile)? => Some(lockfile),
+            _ => None,
+        };
+        paths.extend(lockfile.clone());
+        repo.ensure_clean_except(&paths)?;
+        if let Some(tag) = &tag {
+            if repo.tag_exists(tag)? {
+                bail!("Tag '{}' already exists", tag);
+            }
+        }
+        print_info(
+            &match &tag {
+                Some(tag) => format!("Will commit {} file(s) and tag {}", paths.len(), tag),
+                None => format!("Will commit {} file(s)", paths.len()),
+            },
+            quiet,
+        );
+        Some((repo, paths, tag, lockfile))
+    } else {
+        None
+    };
+
+    if dry_run {
+        print_info("(dry run - no changes made)", false);
+        return Ok(());
+    }
+    for manifest in &manifests {
+        manifest.save()?;
+    }
+    for (file_path, content) in files {
+        fs::write(&file_path, content)
+            .with_context(|| format!("Failed to write {}", file_path.display()))?;
+    }
+    print_success(&format!("Version updated to {}", next), quiet);
+
+    if let Some((repo, paths, tag, lockfile)) = release {
+        let message = release_message(&manifest, &next);
+        if let Some(lockfile) = lockfile {
+            // The manifests are already written, so explain how to finish the
+            // release rather than leaving it half-applied without a word.
+            if let Err(e) = update_lockfile(path) {
+                let changed: Vec<String> = paths
+                    .iter()
+                    .filter(|p| **p != lockfile)
+                    .map(|p| p.display().to_string())
+                    .collect();
+                let tag_step = match &tag {
+                    Some(tag) => format!(" and `git tag -a {} -m \"{}\"`", tag, message),
+                    None => String::new(),
+                };
+                bail!(
+                    "{:#}\nVersion updated to {} in {}, but nothing was committed. Run `cargo update --workspace`, then commit these files and {} with `git commit -m \"{}\"`{}",
+                    e,
+                    next,
+                    changed.join(", "),
+                    lockfile.display(),
+                    message,
+                    tag_step
+                );
+            }
+            print_info(&format!("  {} updated", lockfile.display()), quiet);
+        }
+        repo.commit(&paths, &message)?;
+        print_success(&format!("Committed \"{}\"", message), quiet);
+        if let Some(tag) = tag {
+            repo.tag(&tag, &message)?;
+            print_success(&format!("Tagged {}", tag), quiet);
+        }
+    }
+
+    Ok(())
+}
+
+/// The `Cargo.lock` cargo uses for `manifest_path`: the first one found walking
+/// up from the manifest directory (the workspace root for members).
+fn find_lockfile(manifest_path: &Path) -> Option<PathBuf> {
+    let dir = fs::canonicalize(manifest_path)
+        .ok()?
+        .parent()?
+        .to_path_buf();
+    dir.ancestors()
+        .map(|d| d.join("Cargo.lock"))
+        .find(|l| l.is_file())
+}
+
+/// Rewrites the workspace members' versions in Cargo.lock without touching
+/// any other dependency.
+fn update_lockfile(manifest_path: &Path) -> Result<()> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = std::process::Command::new(cargo)
+        .args(["update", "--workspace", "--offline", "--manifest-path"])
+        .arg(manifest_path)
+        .output()
+        .context("Failed to run cargo")?;
+    if !output.status.success() {
+        bail!(
+            "cargo update --workspace failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// `commit-message` in `[package.metadata.cargoe]` (default `Release {version}`),
+/// also used for the tag annotation.
+fn release_message(manifest: &Manifest, version: &Version) -> String {
+    let format = manifest
+        .cargoe_config()
+        .and_then(|c| c.get("commit-message"))
+        .and_then(|f| f.as_str())
+        .unwrap_or("Release {version}");
+    format
+        .replace("{name}", &manifest.get_package_name().unwrap_or_default())
+        .replace("{version}", &version.to_string())
+}
+
+/// `version sync`: writes `version` into every `version-sync` target, or with
+/// `check` fails if any of them disagree.
+fn sync(
+    manifest: &Manifest,
+    version: &Version,
+    check: bool,
+    dry_run: bool,
+    quiet: bool,
+) -> Result<()> {
+    let files = sync_files(manifest, version)?;
+    if files.is_empty() {
+        print_info(
+            "No version-sync targets configured in [package.metadata.cargoe]",
+            quiet,
+        );
+        return Ok(());
+    }
+
+    let mut drifted = 0;
+    for file in &files {
+        if !file.changed() {
+            print_info(&format!("  {}: {}", file.label, version), quiet);
+            continue;
+        }
+        drifted += 1;
+        if check {
+            print_warning(&file.describe(version));
+        } else {
+            print_info(&format!("  {}", file.describe(version)), quiet);
+            if !dry_run {
+                fs::write(&file.path, &file.updated)
+                    .with_context(|| format!("Failed to write {}", file.path.display()))?;
+            }
+        }
+    }
+
+    if check && drifted > 0 {
+        bail!(
+            "{} file(s) out of sync with package.version {}",
+            drifted,
+            version
+        );
+    }
+    if check {
+        print_success("All version-sync targets match", quiet);
+    } else if drifted > 0 && dry_run {
+        print_info("(dry run - no changes made)", false);
+    } else if drifted > 0 {
+        print_success(&format!("Synced {} file(s) to {}", drifted, version), quiet);
+    } else {
+        print_success("All version-sync targets already match", quiet);
+    }
+    Ok(())
+}
+
+/// Matches a semver version in a `template`.
+const VERSION_PATTERN: &str = r"\d+\.\d+\.\d+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?";
+
+struct SyncFile {
+    label: String,
+    path: PathBuf,
+    /// The versions currently written at each match.
+    found: Vec<String>,
+    original: String,
+    updated: String,
+}
+
+impl SyncFile {
+    fn changed(&self) -> bool {
+        self.original != self.updated
+    }
+
+    fn describe(&self, version: &Version) -> String {
+        let version = version.to_string();
+        let mut stale: Vec<&str> = self
+            .found
+            .iter()
+            .map(|f| f.as_str())
+            .filter(|f| *f != version)
+            .collect();
+        stale.sort();
+        stale.dedup();
+        format!("{}: {} → {}", self.label, stale.join(", "), version)
+    }
+}
+
+/// Reads every `[[package.metadata.cargoe.version-sync]]` target and computes its
+/// content with `version` written in. Each entry has a `file` and either a
+/// `template` containing `{version}`, a `regex` whose first group (or whole match)
+/// is the version, or neither when the file holds only the version.
+fn sync_files(manifest: &Manifest, version: &Version) -> Result<Vec<SyncFile>> {
+    let Some(item) = manifest.cargoe_config().and_then(|c| c.get("version-sync")) else {
+        return Ok(Vec::new());
+    };
+    let entries: Vec<&dyn toml_edit::TableLike> = if let Some(tables) = item.as_array_of_tables() {
+        tables
+            .iter()
+            .map(|t| t as &dyn toml_edit::TableLike)
+            .collect()
+    } else if let Some(array) = item.as_array() {
+        array
+            .iter()
+            .filter_map(|v| v.as_inline_table())
+            .map(|t| t as &dyn toml_edit::TableLike)
+            .collect()
+    } else {
+        bail!("version-sync must be an array of tables");
+    };
+
+    let root = manifest.path.parent().unwrap_or(Path::new("."));
+    let name = manifest.get_package_name().unwrap_or_default();
+    let version = version.to_string();
+    let mut files = Vec::new();
+
+    for entry in entries {
+        let file = entry
+            .get("file")
+            .and_then(|f| f.as_str())
+            .context("version-sync entry is missing 'file'")?;
+        let template = entry.get("template").and_then(|t| t.as_str());
+        let regex = entry.get("regex").and_then(|r| r.as_str());
+
+        let pattern = match (template, regex) {
+            (Some(_), Some(_)) => {
+                bail!("version-sync '{}': set either 'template' or 'regex'", file)
+            }
+            (Some(template), None) => {
+                let template = template.replace("{name}", &name);
+                if !template.contains("{version}") {
+                    bail!("version-sync '{}': template must contain {{version}}", file);
+                }
+                let parts: Vec<String> = template.split("{version}").map(regex::escape).collect();
+                parts.join(&format!("({})", VERSION_PATTERN))
+            }
+            (None, Some(regex)) => regex.to_string(),
+            (None, None) => format!(r"\A\s*({})\s*\z", VERSION_PATTERN),
+        };
+        let pattern = Regex::new(&pattern)
+            .with_context(|| format!("version-sync '{}': invalid regex", file))?;
+
+        let path = root.join(file);
+        let original = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let mut found = Vec::new();
+        let mut updated = String::with_capacity(original.len());
+        let mut last = 0;
+        for captures in pattern.captures_iter(&original) {
+            let Some(m) = captures.get(1).or_else(|| captures.get(0)) else {
+                continue;
+            };
+            found.push(m.as_str().to_string());
+            updated.push_str(&original[last..m.start()]);
+            updated.push_str(&version);
+            last = m.end();
+        }
+        updated.push_str(&original[last..]);
+
+        if found.is_empty() {
+            bail!("version-sync '{}': no version found to update", file);
+        }
+        files.push(SyncFile {
+            label: file.to_string(),
+            path,
+            found,
+            original,
+            updated,
+        });
+    }
+
+    Ok(files)
+}
+
+/// Sets the new version on `workspace.manifests[index]` and rewrites every
+/// in-workspace `path` dependency on it. With `cascade`, crates whose normal or
+/// build dependencies changed get a patch bump, which propagates in turn.
+/// Returns which manifests were modified.
+fn propagate(
+    workspace: &mut Workspace,
+    index: usize,
+    next: &Version,
+    cascade: bool,
+    quiet: bool,
+) -> Result<Vec<bool>> {
+    let manifests = &mut workspace.manifests;
+    let mut changed = vec![false; manifests.len()];
+    let mut queue = VecDeque::new();
+
+    set_version(&mut manifests[index], next)?;
+    changed[index] = true;
+    if manifests[index].package().is_some() {
+        queue.push_back((index, next.clone()));
+    } else {
+        // A virtual root: members inheriting `version.workspace = true` move with it.
+        for (i, manifest) in manifests.iter().enumerate() {
+            if inherits_version(manifest) {
+                queue.push_back((i, next.clone()));
+            }
+        }
+    }
+    let mut bumped: BTreeSet<usize> = queue.iter().map(|(i, _)| *i).collect();
+
+    while let Some((target, version)) = queue.pop_front() {
+        let crate_dir = manifest_dir(&manifests[target]).to_path_buf();
+        let crate_name = label(&manifests[target]);
+        let mut workspace_keys = Vec::new();
+        let mut dependents = BTreeSet::new();
+
+        for (i, manifest) in manifests.iter_mut().enumerate() {
+            let base = manifest_dir(manifest).to_path_buf();
+            let name = label(manifest);
+            let mut updates = Vec::new();
+            for_each_dependency_table_mut(&mut manifest.doc, |section, table| {
+                for (key, dep) in table.iter_mut() {
+                    let Some(dep) = dep.as_table_like_mut() else {
+                        continue;
+                    };
+                    let points_here = dep
+                        .get("path")
+                        .and_then(|p| p.as_str())
+                        .is_some_and(|p| path_points_to(&base, p, &crate_dir));
+                    if !points_here {
+                        continue;
+                    }
+                    let Some(req) = dep.get("version").and_then(|v| v.as_str()) else {
+                        continue;
+                    };
+                    let Some(new_req) = update_requirement(req, &version) else {
+                        print_warning(&format!(
+                            "{}: leaving {}.{} = \"{}\" unchanged (not a single requirement)",
+                            name, section, key, req
+                        ));
+                        continue;
+                    };
+                    if new_req == req {
+                        continue;
+                    }
+                    updates.push(format!("{}.{} {} → {}", section, key, req, new_req));
+                    replace_str(dep.get_mut("version"), &new_req);
+                    match section {
+                        "workspace.dependencies" => workspace_keys.push(key.to_string()),
+                        "dev-dependencies" => {}
+                        _ => {
+                            dependents.insert(i);
+                        }
+                    }
+                }
+            });
+            if !updates.is_empty() {
+                changed[i] = true;
+                for update in updates {
+                    print_info(&format!("  {}: {}", name, update), quiet);
+                }
+            }
+        }
+
+        if !cascade {
+            continue;
+        }
+        // Members pulling the crate through `[workspace.dependencies]` depend on it too.
+        for (i, manifest) in manifests.iter().enumerate() {
+            if inherits_any(manifest, &workspace_keys) {
+                dependents.insert(i);
+            }
+        }
+        for i in dependents {
+            if i == target || bumped.contains(&i) || manifests[i].package().is_none() {
+                continue;
+            }
+            if inherits_version(&manifests[i]) {
+                continue;
+            }
+            let current = current_version(&manifests[i])?;
+            let cascaded = current.bump_patch();
+            print_info(
+                &format!(
+                    "  {}: {} → {} (depends on {})",
+                    label(&manifests[i]),
+                    current,
+                    cascaded,
+                    crate_name
+                ),
+                quiet,
+            );
+            set_version(&mut manifests[i], &cascaded)?;
+            changed[i] = true;
+            bumped.insert(i);
+            queue.push_back((i, cascaded));
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Rewrites a requirement such as `0.1.0`, `=0.1.0` or `^0.1` to the new version,
+/// keeping its operator. Compound (`>=0.1, <0.3`) and wildcard requirements are
+/// left alone.
+fn update_requirement(req: &str, version: &Version) -> Option<String> {
+    let parsed = VersionReq::parse(req).ok()?;
+    let [comparator] = parsed.comparators.as_slice() else {
+        return None;
+    };
+    if comparator.op == Op::Wildcard {
+        return None;
+    }
+    let op = if comparator.explicit_op {
+        comparator.op.as_str()
+    } else {
+        ""
+    };
+    Some(format!("{}{}", op, version))
+}
+
+/// Replaces a string value, keeping its surrounding whitespace and comments.
+pub fn replace_str(item: Option<&mut toml_edit::Item>, new: &str) {
+    if let Some(value) = item.and_then(|v| v.as_value_mut()) {
+        let decor = value.decor().clone();
+        *value = new.into();
+        *value.decor_mut() = decor;
+    }
+}
+
+fn inherits_version(manifest: &Manifest) -> bool {
+    manifest
+        .package()
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.get("workspace"))
+        .and_then(|w| w.as_bool())
+        == Some(true)
+}
+
+/// Whether a non-dev dependency of `manifest` is one of `keys` with `workspace = true`.
+fn inherits_any(manifest: &Manifest, keys: &[String]) -> bool {
+    if keys.is_empty() {
+        return false;
+    }
+    let doc = manifest.doc.as_table();
+    let mut tables: Vec<&dyn toml_edit::TableLike> = Vec::new();
+    for section in ["dependencies", "build-dependencies"] {
+        if let Some(table) = doc.get(section).and_then(|t| t.as_table_like()) {
+            tables.push(table);
+        }
+        if let Some(targets) = doc.get("target").and_then(|t| t.as_table_like()) {
+            tables.extend(
+                targets
+                    .iter()
+                    .filter_map(|(_, t)| t.get(section)?.as_table_like()),
+            );
+        }
+    }
+    tables.iter().any(|table| {
+        keys.iter().any(|key| {
+            table
+                .get(key)
+                .and_then(|d| d.get("workspace"))
+                .and_then(|w| w.as_bool())
+                == Some(true)
+        })
+    })
+}
+
+fn manifest_dir(manifest: &Manifest) -> &Path {
+    manifest.path.parent().unwrap_or(Path::new("."))
+}
+
+fn label(manifest: &Manifest) -> String {
+    manifest
+        .get_package_name()
+        .unwrap_or_else(|| "workspace".to_string())
+}
+
+fn bump(current: &Version, level: BumpLevel, pre_id: &str) -> Result<Version> {
+    let next = match level {
+        BumpLevel::Major => current.bump_major(),
+        BumpLevel::Minor => current.bump_minor(),
+        BumpLevel::Patch => current.bump_patch(),
+        BumpLevel::Breaking => current.bump_breaking(),
+        BumpLevel::Pre => {
+            Version::parse(&format!("0.0.0-{}", pre_id))
+                .map_err(|e| anyhow::anyhow!("Invalid --pre-id '{}': {}", pre_id, e))?;
+            current.bump_pre(pre_id)
+        }
+        BumpLevel::Release => {
+            if !current.is_prerelease() {
+                bail!("{} is not a pre-release", current);
+            }
+            current.release()
+        }
+    };
+    Ok(next)
+}
+
+/// The `[package]` version, or `[workspace.package]` for a virtual manifest.
+pub fn current_version(manifest: &Manifest) -> Result<Version> {
+    let item = match manifest.package() {
+        Some(package) => package.get("version"),
+        None => manifest
+            .doc
+            .get("workspace")
+            .and_then(|w| w.get("package"))
+            .and_then(|p| p.get("version")),
+    };
+    let Some(item) = item else {
+        bail!("No version field found in manifest");
+    };
+    if item
+        .as_table_like()
+        .and_then(|t| t.get("workspace"))
+        .and_then(|w| w.as_bool())
+        == Some(true)
+    {
+        bail!(
+            "Version is inherited from the workspace; run this against the workspace root manifest"
+        );
+    }
+    let version = item.as_str().context("version is not a string")?;
+    Version::parse(version)
+        .map_err(|e| anyhow::anyhow!("Current version '{}' is not valid semver: {}", version, e))
+}
+
+fn set_version(manifest: &mut Manifest, version: &Version) -> Result<()> {
+    let table = if manifest.package().is_some() {
+        manifest.package_mut()?
+    } else {
+        manifest.doc["workspace"]["package"]
+            .as_table_mut()
+            .context("workspace.package is not a table")?
+    };
+    // Keep the key's decor (trailing comments) when replacing the value.
+    if table.contains_key("version") {
+        replace_str(table.get_mut("version"), &version.to_string());
+    } else {
+        table.insert("version", toml_edit::value(version.to_string()));
+    }
+    Ok(())
+}
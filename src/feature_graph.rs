@@ -0,0 +1,195 @@
+// src/feature_graph.rs
+use crate::manifest::Manifest;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A single entry in a feature's list, e.g. `"std"`, `"dep:serde"` or `"serde?/std"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeatureValue {
+    Feature(String),
+    Dep(String),
+    DepFeature {
+        dep: String,
+        feature: String,
+        weak: bool,
+    },
+}
+
+impl FeatureValue {
+    pub fn parse(s: &str) -> Self {
+        if let Some(dep) = s.strip_prefix("dep:") {
+            return FeatureValue::Dep(dep.to_string());
+        }
+        if let Some((dep, feature)) = s.split_once('/') {
+            let (dep, weak) = match dep.strip_suffix('?') {
+                Some(d) => (d, true),
+                None => (dep, false),
+            };
+            return FeatureValue::DepFeature {
+                dep: dep.to_string(),
+                feature: feature.to_string(),
+                weak,
+            };
+        }
+        FeatureValue::Feature(s.to_string())
+    }
+}
+
+/// The `[features]` table together with the dependencies it can refer to.
+pub struct FeatureGraph {
+    /// Explicit features in manifest order.
+    pub features: Vec<(String, Vec<String>)>,
+    /// Dependency names (after renaming) mapped to whether they are optional.
+    pub deps: BTreeMap<String, bool>,
+}
+
+impl FeatureGraph {
+    pub fn from_manifest(manifest: &Manifest) -> Self {
+        let mut features = Vec::new();
+        if let Some(table) = manifest.doc.get("features").and_then(|f| f.as_table_like()) {
+            for (name, value) in table.iter() {
+                let entries = value
+                    .as_array()
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                features.push((name.to_string(), entries));
+            }
+        }
+
+        let mut deps = BTreeMap::new();
+        collect_deps(manifest.doc.as_table(), &mut deps);
+        if let Some(targets) = manifest.doc.get("target").and_then(|t| t.as_table_like()) {
+            for (_, target) in targets.iter() {
+                if let Some(target) = target.as_table_like() {
+                    collect_deps(target, &mut deps);
+                }
+            }
+        }
+
+        Self { features, deps }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty() && !self.deps.values().any(|&optional| optional)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&[String]> {
+        self.features
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, entries)| entries.as_slice())
+    }
+
+    pub fn is_optional_dep(&self, name: &str) -> bool {
+        self.deps.get(name).copied().unwrap_or(false)
+    }
+
+    /// Optional dependencies referenced anywhere with `dep:` syntax. Cargo does not
+    /// create an implicit feature for these.
+    pub fn dep_syntax_deps(&self) -> BTreeSet<String> {
+        self.features
+            .iter()
+            .flat_map(|(_, entries)| entries.iter())
+            .filter_map(|e| match FeatureValue::parse(e) {
+                FeatureValue::Dep(dep) => Some(dep),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Features cargo creates implicitly for optional dependencies (edition 2021 rules).
+    pub fn implicit_features(&self) -> Vec<String> {
+        let explicit_dep = self.dep_syntax_deps();
+        self.deps
+            .iter()
+            .filter(|(name, &optional)| {
+                optional && !explicit_dep.contains(*name) && self.get(name).is_none()
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Whether `name` can be enabled as a feature, explicitly or implicitly.
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.get(name).is_some() || self.implicit_features().iter().any(|f| f == name)
+    }
+
+    /// Feature-to-feature edges, ignoring dependency entries.
+    pub fn feature_edges(&self, name: &str) -> Vec<String> {
+        self.get(name)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|e| match FeatureValue::parse(e) {
+                FeatureValue::Feature(f) if self.get(&f).is_some() => Some(f),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// All features transitively enabled by `roots`, including the roots themselves.
+    pub fn resolve<'a>(&self, roots: impl IntoIterator<Item = &'a str>) -> BTreeSet<String> {
+        let mut enabled = BTreeSet::new();
+        let mut queue: Vec<String> = roots.into_iter().map(|s| s.to_string()).collect();
+        while let Some(name) = queue.pop() {
+            if enabled.insert(name.clone()) {
+                queue.extend(self.feature_edges(&name));
+            }
+        }
+        enabled
+    }
+
+    /// Returns every cycle between explicit features, each as the path of feature
+    /// names with the starting feature repeated at the end.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut cycles = Vec::new();
+        let mut done = BTreeSet::new();
+        for (name, _) in &self.features {
+            let mut stack = Vec::new();
+            self.walk_cycles(name, &mut stack, &mut done, &mut cycles);
+        }
+        cycles
+    }
+
+    fn walk_cycles(
+        &self,
+        name: &str,
+        stack: &mut Vec<String>,
+        done: &mut BTreeSet<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        if let Some(pos) = stack.iter().position(|n| n == name) {
+            let mut cycle = stack[pos..].to_vec();
+            cycle.push(name.to_string());
+            cycles.push(cycle);
+            return;
+        }
+        if done.contains(name) {
+            return;
+        }
+        stack.push(name.to_string());
+        for next in self.feature_edges(name) {
+            self.walk_cycles(&next, stack, done, cycles);
+        }
+        stack.pop();
+        done.insert(name.to_string());
+    }
+}
+
+fn collect_deps(table: &dyn toml_edit::TableLike, deps: &mut BTreeMap<String, bool>) {
+    for section in ["dependencies", "build-dependencies"] {
+        if let Some(section) = table.get(section).and_then(|s| s.as_table_like()) {
+            for (name, spec) in section.iter() {
+                let optional = spec
+                    .as_table_like()
+                    .and_then(|t| t.get("optional"))
+                    .and_then(|o| o.as_bool())
+                    .unwrap_or(false);
+                let entry = deps.entry(name.to_string()).or_insert(false);
+                *entry |= optional;
+            }
+        }
+    }
+}
@@ -0,0 +1,457 @@
+// src/findings.rs
+use crate::utils::closest;
+use serde_json::json;
+use std::collections::HashMap;
+use std::ops::Range;
+use toml_edit::{ImDocument, Item, TableLike, Value};
+
+/// Every rule `validate` reports, with a short description.
+pub const RULES: &[(&str, &str)] = &[
+    ("missing-package", "The manifest has no [package] section"),
+    ("missing-field", "A field crates.io requires is missing"),
+    ("unknown-key", "A key Cargo does not know, likely a typo"),
+    ("package-name", "'name' breaks crates.io's naming rules"),
+    ("package-name-style", "'name' mixes '-' and '_'"),
+    (
+        "package-name-collision",
+        "Another workspace crate's name differs only by '-'/'_'",
+    ),
+    ("missing-repository", "'repository' is not set"),
+    ("missing-readme", "'readme' is not set"),
+    ("missing-keywords", "'keywords' is not set"),
+    ("missing-categories", "'categories' is not set"),
+    ("description-short", "'description' is very short"),
+    (
+        "description-too-long",
+        "'description' is over 160 characters and will be truncated",
+    ),
+    ("keywords", "Keywords break crates.io's rules"),
+    (
+        "categories",
+        "Categories are unknown, duplicated or too many",
+    ),
+    ("license", "'license' is not a valid SPDX expression"),
+    ("url", "A link field is not a clean https URL"),
+    ("homepage-is-repository", "'homepage' repeats 'repository'"),
+    ("version", "'version' is not valid SemVer"),
+    ("edition", "'edition' is not a known Rust edition"),
+    ("rust-version", "'rust-version' is not a valid Rust version"),
+    ("msrv", "'rust-version' is older than the manifest requires"),
+    (
+        "missing-file",
+        "A file referenced from [package] does not exist",
+    ),
+    (
+        "license-and-license-file",
+        "Both 'license' and 'license-file' are set",
+    ),
+    (
+        "invalid-pattern",
+        "An 'include' or 'exclude' pattern is invalid",
+    ),
+    ("unmatched-include", "An 'include' entry matches no file"),
+    (
+        "readme-excluded",
+        "An 'exclude' entry matches the readme, which cargo packages anyway",
+    ),
+    (
+        "invalid-requirement",
+        "A dependency version requirement does not parse",
+    ),
+    (
+        "requirement-build-metadata",
+        "A version requirement carries build metadata",
+    ),
+    (
+        "path-dependency-version",
+        "A path dependency's requirement rejects its version",
+    ),
+    (
+        "feature-shadows-dependency",
+        "A feature hides an optional dependency",
+    ),
+    (
+        "invalid-feature",
+        "A feature entry names nothing that exists",
+    ),
+    ("feature-cycle", "Features enable each other in a cycle"),
+    (
+        "unused-optional-dependency",
+        "No feature enables an optional dependency",
+    ),
+    (
+        "target-name",
+        "A build target has no name or a duplicate one",
+    ),
+    ("target-path", "A build target's source file does not exist"),
+    (
+        "target-required-features",
+        "A target requires an undefined feature",
+    ),
+    (
+        "redundant-target",
+        "A target entry restates an auto-discovered one",
+    ),
+    (
+        "undiscovered-target",
+        "A target file is skipped because auto-discovery is off",
+    ),
+    ("profile", "A [profile] value is invalid"),
+];
+
+/// How a rule is reported, as set by `--allow`/`--warn`/`--deny` or the
+/// `validate` table of cargoe's config.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl Level {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "allow" => Some(Level::Allow),
+            "warn" => Some(Level::Warn),
+            "deny" => Some(Level::Deny),
+            _ => None,
+        }
+    }
+}
+
+/// Fails for a rule id `validate` does not know, suggesting the closest one.
+pub fn check_rule(rule: &str) -> Result<(), String> {
+    if RULES.iter().any(|(id, _)| *id == rule) {
+        return Ok(());
+    }
+    // A truncated id is more likely than a typo.
+    let suggestion = RULES
+        .iter()
+        .map(|(id, _)| *id)
+        .find(|id| id.starts_with(rule))
+        .or_else(|| closest(rule, RULES.iter().map(|(id, _)| *id)));
+    Err(match suggestion {
+        Some(id) => format!("Unknown rule '{}'; did you mean '{}'?", rule, id),
+        None => format!("Unknown rule '{}'", rule),
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// A single problem reported by `validate`.
+pub struct Finding {
+    /// Stable identifier of the check, e.g. `missing-file`.
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    /// TOML key path of the offending entry; array elements use their index.
+    pub key: Vec<String>,
+    /// 1-based line and column of `key` in the manifest, when it could be found.
+    pub position: Option<(usize, usize)>,
+}
+
+impl Finding {
+    pub fn key_path(&self) -> String {
+        key_path(&self.key)
+    }
+}
+
+/// A key path in TOML dotted form, e.g. `target.'cfg(unix)'.dependencies`.
+pub fn key_path<S: AsRef<str>>(key: &[S]) -> String {
+    key.iter()
+        .map(|segment| {
+            let segment = segment.as_ref();
+            let bare = !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if bare {
+                segment.to_string()
+            } else {
+                format!("'{}'", segment)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[derive(Default)]
+pub struct Findings {
+    pub items: Vec<Finding>,
+}
+
+impl Findings {
+    pub fn error(&mut self, rule: &'static str, key: &[&str], message: impl Into<String>) {
+        self.push(Severity::Error, rule, key, message);
+    }
+
+    pub fn warning(&mut self, rule: &'static str, key: &[&str], message: impl Into<String>) {
+        self.push(Severity::Warning, rule, key, message);
+    }
+
+    pub fn push(
+        &mut self,
+        severity: Severity,
+        rule: &'static str,
+        key: &[&str],
+        message: impl Into<String>,
+    ) {
+        self.items.push(Finding {
+            rule,
+            severity,
+            message: message.into(),
+            key: key.iter().map(|s| s.to_string()).collect(),
+            position: None,
+        });
+    }
+
+    /// Drops allowed findings and regrades the rest according to `levels`.
+    pub fn apply_levels(&mut self, levels: &HashMap<String, Level>) {
+        self.items
+            .retain_mut(|finding| match levels.get(finding.rule) {
+                Some(Level::Allow) => false,
+                Some(Level::Warn) => {
+                    finding.severity = Severity::Warning;
+                    true
+                }
+                Some(Level::Deny) => {
+                    finding.severity = Severity::Error;
+                    true
+                }
+                None => true,
+            });
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Finding> {
+        self.items.iter().filter(|f| f.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Finding> {
+        self.items
+            .iter()
+            .filter(|f| f.severity == Severity::Warning)
+    }
+
+    /// Fills in `position` from the manifest source. A key that does not exist
+    /// (such as a missing field) points at its closest existing parent.
+    pub fn locate(&mut self, source: &str) {
+        let Ok(doc) = ImDocument::parse(source) else {
+            return;
+        };
+        for finding in &mut self.items {
+            finding.position = span_of(&doc, &finding.key).map(|span| line_col(source, span.start));
+        }
+    }
+}
+
+fn span_of(doc: &ImDocument<&str>, key: &[String]) -> Option<Range<usize>> {
+    let mut table: Option<&dyn TableLike> = doc.as_item().as_table_like();
+    let mut array: Option<&Item> = None;
+    let mut span = None;
+
+    for segment in key {
+        if let Some(item) = array.take() {
+            let Ok(index) = segment.parse::<usize>() else {
+                break;
+            };
+            match item {
+                Item::ArrayOfTables(tables) => {
+                    let Some(entry) = tables.get(index) else {
+                        break;
+                    };
+                    span = entry.span().or(span);
+                    table = Some(entry);
+                }
+                Item::Value(Value::Array(values)) => {
+                    let Some(value) = values.get(index) else {
+                        break;
+                    };
+                    span = value.span().or(span);
+                    table = value.as_inline_table().map(|t| t as &dyn TableLike);
+                }
+                _ => break,
+            }
+            continue;
+        }
+
+        let Some((found, item)) = table.and_then(|t| t.get_key_value(segment)) else {
+            break;
+        };
+        // A `[table]` is best located by its header, anything else by its key.
+        let own = if item.is_table() {
+            item.span().or_else(|| found.span())
+        } else {
+            found.span()
+        };
+        span = own.or_else(|| item.span()).or(span);
+        table = item.as_table_like();
+        if item.is_array_of_tables() || item.is_array() {
+            array = Some(item);
+        }
+    }
+    span
+}
+
+fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+pub fn to_json(findings: &Findings, manifest: &str) -> String {
+    let items: Vec<_> = findings
+        .items
+        .iter()
+        .map(|f| {
+            json!({
+                "rule": f.rule,
+                "severity": f.severity.as_str(),
+                "message": f.message,
+                "key": f.key_path(),
+                "line": f.position.map(|(line, _)| line),
+                "column": f.position.map(|(_, column)| column),
+            })
+        })
+        .collect();
+    let report = json!({
+        "manifest": manifest,
+        "errors": findings.errors().count(),
+        "warnings": findings.warnings().count(),
+        "findings": items,
+    });
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
+
+/// SARIF 2.1.0, as consumed by GitHub code scanning.
+pub fn to_sarif(findings: &Findings, manifest: &str) -> String {
+    let mut rules: Vec<&str> = findings.items.iter().map(|f| f.rule).collect();
+    rules.sort_unstable();
+    rules.dedup();
+
+    let results: Vec<_> = findings
+        .items
+        .iter()
+        .map(|f| {
+            let mut location = json!({
+                "physicalLocation": {
+                    "artifactLocation": { "uri": manifest },
+                }
+            });
+            if let Some((line, column)) = f.position {
+                location["physicalLocation"]["region"] =
+                    json!({ "startLine": line, "startColumn": column });
+            }
+            json!({
+                "ruleId": f.rule,
+                "level": f.severity.as_str(),
+                "message": { "text": f.message },
+                "locations": [location],
+            })
+        })
+        .collect();
+
+    let report = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "cargoe",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules.iter().map(|id| rule_descriptor(id)).collect::<Vec<_>>(),
+                }
+            },
+            "results": results,
+        }]
+    });
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
+
+fn rule_descriptor(id: &str) -> serde_json::Value {
+    match RULES.iter().find(|(rule, _)| *rule == id) {
+        Some((_, description)) => json!({ "id": id, "shortDescription": { "text": description } }),
+        None => json!({ "id": id }),
+    }
+}
+
+/// JUnit XML with one test case per finding; errors are failures.
+pub fn to_junit(findings: &Findings, manifest: &str) -> String {
+    let errors = findings.errors().count();
+    let tests = findings.items.len().max(1);
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"cargoe validate\" tests=\"{}\" failures=\"{}\">\n",
+        tests, errors
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n",
+        escape_xml(manifest),
+        tests,
+        errors
+    ));
+
+    if findings.items.is_empty() {
+        xml.push_str("    <testcase name=\"manifest\" classname=\"cargoe.validate\"/>\n");
+    }
+    for f in &findings.items {
+        let location = match f.position {
+            Some((line, column)) => format!("{}:{}:{}", manifest, line, column),
+            None => manifest.to_string(),
+        };
+        let name = if f.key.is_empty() {
+            f.rule.to_string()
+        } else {
+            format!("{} ({})", f.rule, f.key_path())
+        };
+        xml.push_str(&format!(
+            "    <testcase name=\"{}\" classname=\"cargoe.validate.{}\">\n",
+            escape_xml(&name),
+            f.rule
+        ));
+        match f.severity {
+            Severity::Error => xml.push_str(&format!(
+                "      <failure type=\"error\" message=\"{}\">{}: {}</failure>\n",
+                escape_xml(&f.message),
+                escape_xml(&location),
+                escape_xml(&f.message)
+            )),
+            Severity::Warning => xml.push_str(&format!(
+                "      <system-out>warning: {}: {}</system-out>\n",
+                escape_xml(&location),
+                escape_xml(&f.message)
+            )),
+        }
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,111 @@
+// src/git.rs
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The local git repository a manifest lives in, driven through the `git` CLI.
+pub struct Repository {
+    root: PathBuf,
+}
+
+impl Repository {
+    pub fn open(manifest_path: &Path) -> Result<Self> {
+        let dir = manifest_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-parse", "--show-toplevel"])
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            bail!("{} is not inside a git repository", dir.display());
+        }
+        let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(Self {
+            root: PathBuf::from(root),
+        })
+    }
+
+    fn run(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(args)
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            bail!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Fails if tracked files other than `paths` have uncommitted changes.
+    pub fn ensure_clean_except(&self, paths: &[PathBuf]) -> Result<()> {
+        let allowed: Vec<PathBuf> = paths.iter().map(|p| canonical(p)).collect();
+        let status = self.run(&["status", "--porcelain=v1", "-z", "--untracked-files=no"])?;
+
+        let mut unrelated = Vec::new();
+        let mut entries = status.split('\0').filter(|e| !e.is_empty());
+        while let Some(entry) = entries.next() {
+            let (code, file) = entry.split_at(3.min(entry.len()));
+            // Renames and copies are followed by the original path.
+            if code.contains('R') || code.contains('C') {
+                entries.next();
+            }
+            if !allowed.contains(&canonical(&self.root.join(file))) {
+                unrelated.push(file.to_string());
+            }
+        }
+
+        if !unrelated.is_empty() {
+            bail!(
+                "Working tree has uncommitted changes unrelated to the release: {}",
+                unrelated.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether `path` is tracked by git.
+    pub fn is_tracked(&self, path: &Path) -> Result<bool> {
+        let path = canonical(path).display().to_string();
+        Ok(!self.run(&["ls-files", "--", &path])?.trim().is_empty())
+    }
+
+    pub fn tag_exists(&self, tag: &str) -> Result<bool> {
+        Ok(!self.run(&["tag", "--list", tag])?.trim().is_empty())
+    }
+
+    pub fn commit(&self, paths: &[PathBuf], message: &str) -> Result<()> {
+        let mut add = vec!["add", "--"];
+        let paths: Vec<String> = paths
+            .iter()
+            .map(|p| canonical(p).display().to_string())
+            .collect();
+        add.extend(paths.iter().map(|p| p.as_str()));
+        self.run(&add)?;
+
+        let mut commit = vec!["commit", "-m", message, "--"];
+        commit.extend(paths.iter().map(|p| p.as_str()));
+        self.run(&commit)?;
+        Ok(())
+    }
+
+    /// Creates an annotated tag on `HEAD`.
+    pub fn tag(&self, tag: &str, message: &str) -> Result<()> {
+        self.run(&["tag", "-a", tag, "-m", message])?;
+        Ok(())
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
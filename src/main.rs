@@ -8,14 +8,26 @@
 #![allow(clippy::collapsible_if)]
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand, ArgAction};
+use clap::{Args, Parser, Subcommand, ArgAction, ValueEnum};
 use clap_version_flag::colorful_version;
 use std::path::PathBuf;
 
+mod build_targets;
 mod commands;
+mod feature_graph;
+mod findings;
+mod git;
 mod manifest;
+mod msrv;
+mod patterns;
+mod schema;
+mod semver;
+mod spdx;
+mod urls;
 mod utils;
+mod workspace;
 
+use build_targets::TargetKind;
 use commands::*;
 
 #[derive(Parser)]
@@ -72,12 +84,43 @@ enum Commands {
     #[command(subcommand)]
     Metadata(MetadataCommands),
 
+    /// Inspect the [features] table
+    #[command(subcommand)]
+    Features(FeaturesCommands),
+
+    /// Manage build targets ([lib], [[bin]], [[example]], [[test]], [[bench]])
+    #[command(subcommand)]
+    Targets(TargetsCommands),
+
+    /// Manage [profile.*] sections
+    #[command(subcommand)]
+    Profile(ProfileCommands),
+
+    /// Manage the [lints] table
+    #[command(subcommand)]
+    Lints(LintsCommands),
+
+    /// Bump or set the package version
+    #[command(subcommand)]
+    Version(VersionCommands),
+
+    /// Maintain a Keep a Changelog CHANGELOG.md
+    #[command(subcommand)]
+    Changelog(ChangelogCommands),
+
+    /// Preview what `cargo package` will ship
+    #[command(subcommand)]
+    Package(PackageCommands),
+
     /// Set package fields (repository, homepage, documentation, etc.)
     Set {
         /// Field to set (repository, homepage, documentation, readme, license-file)
         field: String,
         /// Value to set
         value: String,
+        /// Rewrite a git remote (git@host:org/repo.git) as its https URL
+        #[arg(long)]
+        normalize: bool,
     },
 
     /// Get package field value
@@ -91,6 +134,14 @@ enum Commands {
         /// Check against crates.io requirements
         #[arg(long)]
         strict: bool,
+        /// Output format for the findings
+        #[arg(long, value_enum, default_value_t = ValidateFormat::Text)]
+        format: ValidateFormat,
+        #[command(flatten)]
+        rules: RuleOptions,
+        /// Apply safe fixes (SPDX ids, keywords, categories, documentation, readme)
+        #[arg(long)]
+        fix: bool,
     },
 
     /// Format Cargo.toml
@@ -205,6 +256,287 @@ enum MetadataCommands {
     Clear,
 }
 
+#[derive(Subcommand)]
+enum FeaturesCommands {
+    /// Render features, the optional dependencies they enable and `default` as a graph
+    Graph {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+    },
+    /// Write a feature table into README.md between marker comments
+    Doc {
+        /// Check that the table is up to date without modifying README.md
+        #[arg(long)]
+        check: bool,
+    },
+    /// Compute a reduced feature powerset for CI (use with --no-default-features)
+    Matrix {
+        /// Maximum number of features per combination
+        #[arg(long, default_value_t = features::DEFAULT_MATRIX_DEPTH)]
+        depth: usize,
+        /// Comma-separated group of mutually exclusive features (repeatable)
+        #[arg(long, value_name = "FEATURES")]
+        exclusive: Vec<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = MatrixFormat::Json)]
+        format: MatrixFormat,
+    },
+}
+
+#[derive(Subcommand)]
+enum TargetsCommands {
+    /// Declare a build target
+    Add {
+        /// Target kind
+        #[arg(value_enum)]
+        kind: TargetKind,
+        /// Target name
+        name: String,
+        /// Source path (defaults to cargo's conventional location)
+        #[arg(long)]
+        path: Option<String>,
+    },
+    /// Remove a declared build target
+    Remove {
+        #[arg(value_enum)]
+        kind: TargetKind,
+        name: String,
+    },
+    /// List declared and auto-discovered build targets
+    List,
+    /// Set a target key (path, required-features, harness, crate-type, doc)
+    Set {
+        #[arg(value_enum)]
+        kind: TargetKind,
+        name: String,
+        key: String,
+        /// Value to set (comma-separated for required-features and crate-type)
+        value: String,
+    },
+    /// Remove a key from a target
+    Unset {
+        #[arg(value_enum)]
+        kind: TargetKind,
+        name: String,
+        key: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileCommands {
+    /// List profiles defined in the manifest
+    List,
+    /// Set a profile key (opt-level, debug, lto, codegen-units, strip, panic, ...)
+    Set {
+        /// Profile name (dev, release, test, bench or a custom profile)
+        profile: String,
+        key: String,
+        /// Value to set (parsed as TOML, e.g. 3, true, "z")
+        value: String,
+    },
+    /// Remove a key from a profile
+    Unset { profile: String, key: String },
+    /// Create a custom profile
+    Create {
+        profile: String,
+        /// Profile to inherit settings from
+        #[arg(long, default_value = "release")]
+        inherits: String,
+    },
+    /// Remove a profile
+    Remove { profile: String },
+    /// Write a known-good block of settings into a profile
+    Preset {
+        #[arg(value_enum)]
+        preset: ProfilePreset,
+        /// Profile to write to (release for min-size/max-perf, dev for fast-debug)
+        #[arg(long)]
+        profile: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ProfilePreset {
+    /// Smallest binary: opt-level = "z", lto, one codegen unit, strip, panic = "abort"
+    MinSize,
+    /// Fastest code: opt-level = 3, fat LTO, one codegen unit
+    MaxPerf,
+    /// Quicker debug builds: opt-level = 1, line-tables-only debug info
+    FastDebug,
+}
+
+#[derive(Subcommand)]
+enum LintsCommands {
+    /// Set a lint level
+    Set {
+        #[arg(value_enum)]
+        tool: LintTool,
+        /// Lint or lint group name (e.g. unsafe_code, pedantic)
+        lint: String,
+        #[arg(value_enum)]
+        level: LintLevel,
+        /// Priority, lower applies first (use -1 for lint groups)
+        #[arg(long, allow_hyphen_values = true)]
+        priority: Option<i64>,
+        /// Edit [workspace.lints] instead of [lints]
+        #[arg(long)]
+        workspace: bool,
+    },
+    /// Remove a lint
+    Remove {
+        #[arg(value_enum)]
+        tool: LintTool,
+        lint: String,
+        /// Edit [workspace.lints] instead of [lints]
+        #[arg(long)]
+        workspace: bool,
+    },
+    /// List configured lints
+    List,
+    /// Inherit lints from [workspace.lints] (workspace = true)
+    Inherit,
+    /// Move crate-level lint attributes from lib.rs/main.rs into [lints]
+    Migrate {
+        /// Leave the attributes in the source files
+        #[arg(long)]
+        keep_attributes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum VersionCommands {
+    /// Compute the next version and write it to the manifest
+    Bump {
+        #[arg(value_enum)]
+        level: BumpLevel,
+        /// Pre-release identifier used by `pre`
+        #[arg(long, default_value = "rc")]
+        pre_id: String,
+        #[command(flatten)]
+        options: VersionOptions,
+    },
+    /// Set an explicit version
+    Set {
+        /// New version (full semver, e.g. 1.2.0-beta.1)
+        version: String,
+        #[command(flatten)]
+        options: VersionOptions,
+    },
+    /// Write package.version into the files listed under version-sync
+    Sync {
+        /// Fail if any file differs instead of updating it
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+#[derive(Args)]
+struct VersionOptions {
+    /// Update path dependency requirements on this crate across the workspace
+    #[arg(long)]
+    propagate: bool,
+    /// Also patch-bump crates whose dependency requirements changed (implies --propagate)
+    #[arg(long)]
+    cascade: bool,
+    /// Release the [Unreleased] section of the changelog under the new version
+    #[arg(long)]
+    changelog: bool,
+    /// Commit the changed files to the local git repository
+    #[arg(long)]
+    commit: bool,
+    /// Create an annotated tag for the release commit (see tag-format)
+    #[arg(long, requires = "commit")]
+    tag: bool,
+}
+
+#[derive(Args)]
+struct RuleOptions {
+    /// Report a rule as an error (overrides --warn and --allow)
+    #[arg(long, value_name = "RULE")]
+    deny: Vec<String>,
+    /// Report a rule as a warning (overrides --allow)
+    #[arg(long, value_name = "RULE")]
+    warn: Vec<String>,
+    /// Do not report a rule
+    #[arg(long, value_name = "RULE")]
+    allow: Vec<String>,
+}
+
+#[derive(Subcommand)]
+enum ChangelogCommands {
+    /// Turn [Unreleased] into a dated release section and update compare links
+    Release {
+        /// Version to release (defaults to package.version)
+        version: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PackageCommands {
+    /// List the files include/exclude select, with sizes and a compressed estimate
+    List,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BumpLevel {
+    /// 1.2.3 -> 2.0.0
+    Major,
+    /// 1.2.3 -> 1.3.0
+    Minor,
+    /// 1.2.3 -> 1.2.4
+    Patch,
+    /// Next pre-release: 1.2.3 -> 1.2.4-rc.1, 1.2.4-rc.1 -> 1.2.4-rc.2
+    Pre,
+    /// Drop the pre-release: 1.2.4-rc.2 -> 1.2.4
+    Release,
+    /// Next incompatible version: major on 1.x+, minor on 0.x, patch on 0.0.x
+    Breaking,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LintTool {
+    Rust,
+    Clippy,
+    Rustdoc,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ValidateFormat {
+    /// Coloured report for the terminal
+    Text,
+    /// JSON list of findings
+    Json,
+    /// SARIF 2.1.0 for code scanning
+    Sarif,
+    /// JUnit XML for test dashboards
+    Junit,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum MatrixFormat {
+    /// JSON array of feature lists
+    Json,
+    /// GitHub Actions `strategy.matrix` snippet
+    Github,
+}
+
 // fn print_version() {
 //     println!(
 //         "cargoe v{} by Hadi Cahyadi <cumulu13@gmail.com>",
@@ -255,11 +587,43 @@ fn main() -> Result<()> {
         Commands::Metadata(cmd) => {
             metadata::handle(&cli.manifest_path, cmd, cli.dry_run, cli.quiet)
         }
-        Commands::Set { field, value } => {
-            set::handle(&cli.manifest_path, &field, &value, cli.dry_run, cli.quiet)
+        Commands::Features(cmd) => {
+            features::handle(&cli.manifest_path, cmd, cli.dry_run, cli.quiet)
+        }
+        Commands::Targets(cmd) => targets::handle(&cli.manifest_path, cmd, cli.dry_run, cli.quiet),
+        Commands::Profile(cmd) => profile::handle(&cli.manifest_path, cmd, cli.dry_run, cli.quiet),
+        Commands::Lints(cmd) => lints::handle(&cli.manifest_path, cmd, cli.dry_run, cli.quiet),
+        Commands::Version(cmd) => version::handle(&cli.manifest_path, cmd, cli.dry_run, cli.quiet),
+        Commands::Changelog(cmd) => {
+            changelog::handle(&cli.manifest_path, cmd, cli.dry_run, cli.quiet)
         }
+        Commands::Package(cmd) => package::handle(&cli.manifest_path, cmd),
+        Commands::Set {
+            field,
+            value,
+            normalize,
+        } => set::handle(
+            &cli.manifest_path,
+            &field,
+            &value,
+            normalize,
+            cli.dry_run,
+            cli.quiet,
+        ),
         Commands::Get { field } => get::handle(&cli.manifest_path, &field),
-        Commands::Validate { strict } => validate::handle(&cli.manifest_path, strict),
+        Commands::Validate {
+            strict,
+            format,
+            rules,
+            fix,
+        } => validate::handle(
+            &cli.manifest_path,
+            strict,
+            format,
+            rules,
+            fix,
+            cli.dry_run,
+        ),
         Commands::Fmt { check } => fmt::handle(&cli.manifest_path, check, cli.dry_run),
         Commands::Info => info::handle(&cli.manifest_path),
         Commands::Init { yes } => init::handle(&cli.manifest_path, yes, cli.dry_run),
@@ -40,16 +40,26 @@ impl Manifest {
         self.doc.get("package")?.as_table()
     }
 
-    #[allow(dead_code)]
     pub fn get_package_name(&self) -> Option<String> {
         self.package()?.get("name")?.as_str().map(|s| s.to_string())
     }
 
-    #[allow(dead_code)]
     pub fn get_package_version(&self) -> Option<String> {
         self.package()?
             .get("version")?
             .as_str()
             .map(|s| s.to_string())
     }
+
+    /// cargoe's own settings from `[package.metadata.cargoe]`, falling back to
+    /// `[workspace.metadata.cargoe]`.
+    pub fn cargoe_config(&self) -> Option<&dyn toml_edit::TableLike> {
+        ["package", "workspace"].iter().find_map(|section| {
+            self.doc
+                .get(section)?
+                .get("metadata")?
+                .get("cargoe")?
+                .as_table_like()
+        })
+    }
 }
@@ -0,0 +1,181 @@
+// src/msrv.rs
+use crate::feature_graph::{FeatureGraph, FeatureValue};
+use crate::manifest::Manifest;
+use crate::workspace::dependency_tables;
+use std::fmt;
+
+pub const EDITIONS: &[&str] = &["2015", "2018", "2021", "2024"];
+
+/// A Rust toolchain version as written in `rust-version`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RustVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl RustVersion {
+    pub const fn new(major: u64, minor: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch: 0,
+        }
+    }
+
+    /// Parses `1.70` or `1.70.1`; Cargo allows neither pre-release tags nor
+    /// operators here.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = s.split('.').collect();
+        if !(2..=3).contains(&parts.len()) {
+            return Err("expected MAJOR.MINOR or MAJOR.MINOR.PATCH".to_string());
+        }
+        let mut numbers = [0; 3];
+        for (number, part) in numbers.iter_mut().zip(&parts) {
+            let valid = !part.is_empty()
+                && part.chars().all(|c| c.is_ascii_digit())
+                && (part.len() == 1 || !part.starts_with('0'));
+            if !valid {
+                return Err(format!("'{}' is not a version number", part));
+            }
+            *number = part
+                .parse()
+                .map_err(|_| format!("'{}' is too large", part))?;
+        }
+        Ok(Self {
+            major: numbers[0],
+            minor: numbers[1],
+            patch: numbers[2],
+        })
+    }
+}
+
+impl fmt::Display for RustVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.patch == 0 {
+            write!(f, "{}.{}", self.major, self.minor)
+        } else {
+            write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+        }
+    }
+}
+
+/// The first Rust release that reads the `[lints]` table.
+pub const LINTS_TABLE: RustVersion = RustVersion::new(1, 74);
+
+/// The first Rust release that understands `edition`.
+pub fn edition_requirement(edition: &str) -> Option<RustVersion> {
+    match edition {
+        "2015" => Some(RustVersion::new(1, 0)),
+        "2018" => Some(RustVersion::new(1, 31)),
+        "2021" => Some(RustVersion::new(1, 56)),
+        "2024" => Some(RustVersion::new(1, 85)),
+        _ => None,
+    }
+}
+
+/// Something in the manifest that older toolchains cannot read.
+pub struct Requirement {
+    pub version: RustVersion,
+    pub reason: String,
+    /// TOML key path of what needs `version`.
+    pub key: Vec<String>,
+}
+
+fn requirement(major: u64, minor: u64, reason: impl Into<String>, key: &[&str]) -> Requirement {
+    Requirement {
+        version: RustVersion::new(major, minor),
+        reason: reason.into(),
+        key: key.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+/// The manifest features with a known minimum Rust version.
+pub fn requirements(manifest: &Manifest) -> Vec<Requirement> {
+    let mut found = Vec::new();
+    let package = manifest.package();
+
+    if let Some(edition) = package
+        .and_then(|p| p.get("edition"))
+        .and_then(|e| e.as_str())
+    {
+        if let Some(version) = edition_requirement(edition) {
+            found.push(Requirement {
+                version,
+                reason: format!("edition {}", edition),
+                key: vec!["package".to_string(), "edition".to_string()],
+            });
+        }
+    }
+
+    if let Some(package) = package {
+        if let Some((key, _)) = package
+            .iter()
+            .find(|(_, value)| value.get("workspace").and_then(|w| w.as_bool()) == Some(true))
+        {
+            found.push(requirement(
+                1,
+                64,
+                "workspace inheritance",
+                &["package", key],
+            ));
+        }
+    }
+
+    let inherited_dep =
+        dependency_tables(&manifest.doc)
+            .into_iter()
+            .find_map(|(table_key, table)| {
+                table
+                    .iter()
+                    .find(|(_, dep)| dep.get("workspace").and_then(|w| w.as_bool()) == Some(true))
+                    .map(|(name, _)| [table_key, vec![name]].concat())
+            });
+    if let Some(key) = inherited_dep {
+        found.push(requirement(1, 64, "workspace inheritance", &key));
+    }
+
+    let graph = FeatureGraph::from_manifest(manifest);
+    let namespaced = graph.features.iter().find_map(|(name, entries)| {
+        entries
+            .iter()
+            .position(|e| match FeatureValue::parse(e) {
+                FeatureValue::Dep(_) => true,
+                FeatureValue::DepFeature { weak, .. } => weak,
+                FeatureValue::Feature(_) => false,
+            })
+            .map(|i| (name, i))
+    });
+    if let Some((name, i)) = namespaced {
+        found.push(requirement(
+            1,
+            60,
+            "'dep:' and weak ('?') feature syntax",
+            &["features", name, &i.to_string()],
+        ));
+    }
+
+    if manifest.doc.contains_key("lints") {
+        found.push(Requirement {
+            version: LINTS_TABLE,
+            reason: "the [lints] table".to_string(),
+            key: vec!["lints".to_string()],
+        });
+    }
+
+    for section in ["package", "workspace"] {
+        let resolver = manifest
+            .doc
+            .get(section)
+            .and_then(|s| s.get("resolver"))
+            .and_then(|r| r.as_str());
+        let key = [section, "resolver"];
+        match resolver {
+            Some("2") => found.push(requirement(1, 51, "resolver = \"2\"", &key)),
+            Some("3") => found.push(requirement(1, 84, "resolver = \"3\"", &key)),
+            _ => {}
+        }
+    }
+
+    found
+}
@@ -0,0 +1,156 @@
+// src/patterns.rs
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+
+/// A gitignore-style pattern as used by `include` and `exclude`.
+pub struct Pattern {
+    pub source: String,
+    regex: Regex,
+    dir_only: bool,
+    pub negated: bool,
+}
+
+impl Pattern {
+    pub fn new(source: &str) -> Result<Self, String> {
+        let (negated, pattern) = match source.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, source),
+        };
+        let (dir_only, pattern) = match pattern.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        // A slash anywhere but the end anchors the pattern to the package root.
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+        if pattern.is_empty() {
+            return Err(format!("pattern '{}' is empty", source));
+        }
+
+        let mut body = String::new();
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '*' if chars.get(i + 1) == Some(&'*') => {
+                    let at_start = i == 0 || chars[i - 1] == '/';
+                    if at_start && chars.get(i + 2) == Some(&'/') {
+                        body.push_str("(?:.*/)?");
+                        i += 3;
+                    } else {
+                        body.push_str(".*");
+                        i += 2;
+                    }
+                    continue;
+                }
+                '*' => body.push_str("[^/]*"),
+                '?' => body.push_str("[^/]"),
+                '[' => match chars[i..].iter().position(|&c| c == ']') {
+                    Some(end) if end > 1 => {
+                        let class: String = chars[i + 1..i + end].iter().collect();
+                        let class = match class.strip_prefix('!') {
+                            Some(rest) => format!("^{}", rest),
+                            None => class,
+                        };
+                        body.push('[');
+                        body.push_str(&class.replace('\\', "\\\\"));
+                        body.push(']');
+                        i += end + 1;
+                        continue;
+                    }
+                    _ => body.push_str(r"\["),
+                },
+                c => body.push_str(&regex::escape(&c.to_string())),
+            }
+            i += 1;
+        }
+
+        let regex = if anchored {
+            format!("^{}$", body)
+        } else {
+            format!("^(?:.*/)?{}$", body)
+        };
+        let regex =
+            Regex::new(&regex).map_err(|e| format!("pattern '{}' is invalid: {}", source, e))?;
+
+        Ok(Self {
+            source: source.to_string(),
+            regex,
+            dir_only,
+            negated,
+        })
+    }
+
+    /// Whether the pattern matches `path` (relative, `/`-separated) or one of
+    /// its parent directories.
+    pub fn matches(&self, path: &str) -> bool {
+        let mut prefix = String::new();
+        let parts: Vec<&str> = path.split('/').collect();
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                prefix.push('/');
+            }
+            prefix.push_str(part);
+            let is_dir = i + 1 < parts.len();
+            if (is_dir || !self.dir_only) && self.regex.is_match(&prefix) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Applies a list of patterns in order; a later `!pattern` re-includes paths.
+pub fn matches_any<'a>(patterns: &'a [Pattern], path: &str) -> Option<&'a Pattern> {
+    let mut matched = None;
+    for pattern in patterns {
+        if pattern.matches(path) {
+            matched = if pattern.negated { None } else { Some(pattern) };
+        }
+    }
+    matched
+}
+
+/// Every file under `root` as a relative `/`-separated path, skipping `target`
+/// and version control directories.
+pub fn walk_files(root: &Path) -> Vec<String> {
+    let root = if root.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        root
+    };
+    let mut files = Vec::new();
+    walk(root, "", &mut files);
+    files.sort();
+    files
+}
+
+fn walk(dir: &Path, prefix: &str, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let relative = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}/{}", prefix, name)
+        };
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if prefix.is_empty() && name == "target" || name == ".git" {
+                continue;
+            }
+            // Nested packages are published separately.
+            if entry.path().join("Cargo.toml").is_file() {
+                continue;
+            }
+            walk(&entry.path(), &relative, files);
+        } else {
+            files.push(relative);
+        }
+    }
+}
@@ -0,0 +1,326 @@
+// src/schema.rs
+use crate::utils::closest;
+use crate::workspace::dependency_tables;
+use toml_edit::{DocumentMut, TableLike};
+
+/// Top-level manifest tables and keys.
+pub const ROOT_KEYS: &[&str] = &[
+    "cargo-features",
+    "package",
+    "project",
+    "lib",
+    "bin",
+    "example",
+    "test",
+    "bench",
+    "dependencies",
+    "dev-dependencies",
+    "dev_dependencies",
+    "build-dependencies",
+    "build_dependencies",
+    "target",
+    "features",
+    "patch",
+    "replace",
+    "profile",
+    "workspace",
+    "badges",
+    "lints",
+];
+
+pub const PACKAGE_KEYS: &[&str] = &[
+    "name",
+    "version",
+    "authors",
+    "edition",
+    "rust-version",
+    "description",
+    "documentation",
+    "readme",
+    "homepage",
+    "repository",
+    "license",
+    "license-file",
+    "keywords",
+    "categories",
+    "workspace",
+    "build",
+    "links",
+    "exclude",
+    "include",
+    "publish",
+    "metadata",
+    "default-run",
+    "autolib",
+    "autobins",
+    "autoexamples",
+    "autotests",
+    "autobenches",
+    "resolver",
+];
+
+/// Keys of a detailed dependency (`foo = { ... }` or `[dependencies.foo]`).
+pub const DEPENDENCY_KEYS: &[&str] = &[
+    "version",
+    "path",
+    "git",
+    "branch",
+    "tag",
+    "rev",
+    "registry",
+    "registry-index",
+    "package",
+    "features",
+    "optional",
+    "default-features",
+    "default_features",
+    "workspace",
+    "public",
+    "artifact",
+    "lib",
+    "target",
+];
+
+/// Keys of `[lib]`, `[[bin]]`, `[[example]]`, `[[test]]` and `[[bench]]`.
+pub const TARGET_KEYS: &[&str] = &[
+    "name",
+    "path",
+    "test",
+    "doctest",
+    "bench",
+    "doc",
+    "plugin",
+    "proc-macro",
+    "proc_macro",
+    "harness",
+    "edition",
+    "crate-type",
+    "crate_type",
+    "required-features",
+];
+
+/// Keys of a `[target.'cfg(..)']` table.
+pub const PLATFORM_KEYS: &[&str] = &[
+    "dependencies",
+    "dev-dependencies",
+    "dev_dependencies",
+    "build-dependencies",
+    "build_dependencies",
+];
+
+pub const PROFILE_KEYS: &[&str] = &[
+    "opt-level",
+    "debug",
+    "split-debuginfo",
+    "strip",
+    "debug-assertions",
+    "overflow-checks",
+    "lto",
+    "panic",
+    "incremental",
+    "codegen-units",
+    "rpath",
+    "inherits",
+    "package",
+    "build-override",
+];
+
+pub const WORKSPACE_KEYS: &[&str] = &[
+    "members",
+    "exclude",
+    "default-members",
+    "resolver",
+    "package",
+    "dependencies",
+    "lints",
+    "metadata",
+];
+
+/// `[workspace.package]` keys members can inherit.
+pub const WORKSPACE_PACKAGE_KEYS: &[&str] = &[
+    "authors",
+    "badges",
+    "categories",
+    "description",
+    "documentation",
+    "edition",
+    "exclude",
+    "homepage",
+    "include",
+    "keywords",
+    "license",
+    "license-file",
+    "publish",
+    "readme",
+    "repository",
+    "rust-version",
+    "version",
+];
+
+/// A key Cargo does not know, with the known key it most likely means.
+pub struct UnknownKey {
+    /// Key path of the unknown key itself.
+    pub key: Vec<String>,
+    /// Where it was found, e.g. `[package]`.
+    pub table: String,
+    pub suggestion: Option<&'static str>,
+}
+
+pub fn suggest(known: &'static [&'static str], key: &str) -> Option<&'static str> {
+    closest(key, known.iter().copied())
+}
+
+/// Every key in the manifest that is not part of Cargo's schema. `metadata`
+/// tables, `[badges]` and lint names are free-form and not checked.
+pub fn unknown_keys(doc: &DocumentMut) -> Vec<UnknownKey> {
+    let mut found = Vec::new();
+    let root = doc.as_table();
+    check(root, ROOT_KEYS, &[], "the manifest root", &mut found);
+
+    for section in ["package", "project"] {
+        if let Some(package) = root.get(section).and_then(|p| p.as_table_like()) {
+            check(
+                package,
+                PACKAGE_KEYS,
+                &[section],
+                &format!("[{}]", section),
+                &mut found,
+            );
+        }
+    }
+
+    if let Some(lib) = root.get("lib").and_then(|l| l.as_table_like()) {
+        check(lib, TARGET_KEYS, &["lib"], "[lib]", &mut found);
+    }
+    for section in ["bin", "example", "test", "bench"] {
+        let Some(tables) = root.get(section).and_then(|t| t.as_array_of_tables()) else {
+            continue;
+        };
+        for (i, table) in tables.iter().enumerate() {
+            let index = i.to_string();
+            let label = format!("[[{}]] #{}", section, i + 1);
+            check(table, TARGET_KEYS, &[section, &index], &label, &mut found);
+        }
+    }
+
+    if let Some(targets) = root.get("target").and_then(|t| t.as_table_like()) {
+        for (name, platform) in targets.iter() {
+            if let Some(platform) = platform.as_table_like() {
+                let label = format!("[target.'{}']", name);
+                check(
+                    platform,
+                    PLATFORM_KEYS,
+                    &["target", name],
+                    &label,
+                    &mut found,
+                );
+            }
+        }
+    }
+
+    for (table_key, table) in dependency_tables(doc) {
+        for (name, dep) in table.iter() {
+            if let Some(dep) = dep.as_table_like() {
+                let key = [table_key.as_slice(), &[name]].concat();
+                let label = format!("dependency '{}'", name);
+                check(dep, DEPENDENCY_KEYS, &key, &label, &mut found);
+            }
+        }
+    }
+    if let Some(patches) = root.get("patch").and_then(|p| p.as_table_like()) {
+        for (source, patch) in patches.iter() {
+            let Some(patch) = patch.as_table_like() else {
+                continue;
+            };
+            for (name, dep) in patch.iter() {
+                if let Some(dep) = dep.as_table_like() {
+                    let label = format!("patch '{}'", name);
+                    check(
+                        dep,
+                        DEPENDENCY_KEYS,
+                        &["patch", source, name],
+                        &label,
+                        &mut found,
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(profiles) = root.get("profile").and_then(|p| p.as_table_like()) {
+        for (name, profile) in profiles.iter() {
+            let Some(profile) = profile.as_table_like() else {
+                continue;
+            };
+            let label = format!("[profile.{}]", name);
+            check(
+                profile,
+                PROFILE_KEYS,
+                &["profile", name],
+                &label,
+                &mut found,
+            );
+
+            let overrides = profile
+                .get("package")
+                .and_then(|p| p.as_table_like())
+                .into_iter()
+                .flat_map(|packages| packages.iter())
+                .filter_map(|(package, table)| Some((package, table.as_table_like()?)));
+            for (package, table) in overrides {
+                let key = ["profile", name, "package", package];
+                let label = format!("[profile.{}.package.{}]", name, package);
+                check(table, PROFILE_KEYS, &key, &label, &mut found);
+            }
+            if let Some(table) = profile
+                .get("build-override")
+                .and_then(|t| t.as_table_like())
+            {
+                let key = ["profile", name, "build-override"];
+                let label = format!("[profile.{}.build-override]", name);
+                check(table, PROFILE_KEYS, &key, &label, &mut found);
+            }
+        }
+    }
+
+    if let Some(workspace) = root.get("workspace").and_then(|w| w.as_table_like()) {
+        check(
+            workspace,
+            WORKSPACE_KEYS,
+            &["workspace"],
+            "[workspace]",
+            &mut found,
+        );
+        if let Some(package) = workspace.get("package").and_then(|p| p.as_table_like()) {
+            let key = ["workspace", "package"];
+            check(
+                package,
+                WORKSPACE_PACKAGE_KEYS,
+                &key,
+                "[workspace.package]",
+                &mut found,
+            );
+        }
+    }
+
+    found
+}
+
+fn check(
+    table: &dyn TableLike,
+    known: &'static [&'static str],
+    key: &[&str],
+    label: &str,
+    found: &mut Vec<UnknownKey>,
+) {
+    for (name, _) in table.iter() {
+        if known.contains(&name) {
+            continue;
+        }
+        found.push(UnknownKey {
+            key: key.iter().chain(&[name]).map(|s| s.to_string()).collect(),
+            table: label.to_string(),
+            suggestion: suggest(known, name),
+        });
+    }
+}
@@ -0,0 +1,466 @@
+// src/semver.rs
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A SemVer 2.0.0 version: `MAJOR.MINOR.PATCH[-PRERELEASE][+BUILD]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Vec<Identifier>,
+    pub build: Vec<String>,
+}
+
+/// A dot-separated pre-release identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identifier::Numeric(n) => write!(f, "{}", n),
+            Identifier::AlphaNumeric(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Identifier::Numeric(a), Identifier::Numeric(b)) => a.cmp(b),
+            (Identifier::Numeric(_), Identifier::AlphaNumeric(_)) => Ordering::Less,
+            (Identifier::AlphaNumeric(_), Identifier::Numeric(_)) => Ordering::Greater,
+            (Identifier::AlphaNumeric(a), Identifier::AlphaNumeric(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Version {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: Vec::new(),
+            build: Vec::new(),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        if s.is_empty() {
+            return Err("version is empty".to_string());
+        }
+
+        let (rest, build) = match s.split_once('+') {
+            Some((rest, build)) => (rest, Some(build)),
+            None => (s, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (rest, None),
+        };
+
+        let parts: Vec<&str> = core.split('.').collect();
+        if parts.len() != 3 {
+            return Err(format!(
+                "expected MAJOR.MINOR.PATCH, found {} part(s) in '{}'",
+                parts.len(),
+                core
+            ));
+        }
+        let major = parse_numeric(parts[0], "major")?;
+        let minor = parse_numeric(parts[1], "minor")?;
+        let patch = parse_numeric(parts[2], "patch")?;
+
+        let mut version = Version::new(major, minor, patch);
+
+        if let Some(pre) = pre {
+            for ident in pre.split('.') {
+                check_identifier(ident, "pre-release")?;
+                if ident.bytes().all(|b| b.is_ascii_digit()) {
+                    version
+                        .pre
+                        .push(Identifier::Numeric(parse_numeric(ident, "pre-release")?));
+                } else {
+                    version
+                        .pre
+                        .push(Identifier::AlphaNumeric(ident.to_string()));
+                }
+            }
+        }
+
+        if let Some(build) = build {
+            for ident in build.split('.') {
+                check_identifier(ident, "build metadata")?;
+                version.build.push(ident.to_string());
+            }
+        }
+
+        Ok(version)
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    fn clear_suffixes(&mut self) {
+        self.pre.clear();
+        self.build.clear();
+    }
+
+    /// Next major release. A pre-release of `X.0.0` is released as `X.0.0`.
+    pub fn bump_major(&self) -> Self {
+        let mut next = self.clone();
+        if !(self.is_prerelease() && self.minor == 0 && self.patch == 0) {
+            next.major += 1;
+            next.minor = 0;
+            next.patch = 0;
+        }
+        next.clear_suffixes();
+        next
+    }
+
+    /// Next minor release. A pre-release of `X.Y.0` is released as `X.Y.0`.
+    pub fn bump_minor(&self) -> Self {
+        let mut next = self.clone();
+        if !(self.is_prerelease() && self.patch == 0) {
+            next.minor += 1;
+            next.patch = 0;
+        }
+        next.clear_suffixes();
+        next
+    }
+
+    /// Next patch release. A pre-release is released as its own version.
+    pub fn bump_patch(&self) -> Self {
+        let mut next = self.clone();
+        if !self.is_prerelease() {
+            next.patch += 1;
+        }
+        next.clear_suffixes();
+        next
+    }
+
+    /// Next incompatible release following Cargo's rules: the left-most non-zero
+    /// component is the one that signals breaking changes (`0.3.1` -> `0.4.0`).
+    pub fn bump_breaking(&self) -> Self {
+        if self.major > 0 {
+            self.bump_major()
+        } else if self.minor > 0 {
+            self.bump_minor()
+        } else {
+            self.bump_patch()
+        }
+    }
+
+    /// Next pre-release: `1.0.0-rc.1` -> `1.0.0-rc.2`, `1.0.0` -> `1.0.1-rc.1`.
+    pub fn bump_pre(&self, pre_id: &str) -> Self {
+        let mut next = self.clone();
+        next.build.clear();
+
+        let same_id =
+            matches!(self.pre.first(), Some(Identifier::AlphaNumeric(id)) if id == pre_id);
+        if same_id {
+            match next.pre.last_mut() {
+                Some(Identifier::Numeric(n)) if self.pre.len() > 1 => *n += 1,
+                _ => next.pre.push(Identifier::Numeric(1)),
+            }
+            return next;
+        }
+
+        if !self.is_prerelease() {
+            next.patch += 1;
+        }
+        next.pre = vec![
+            Identifier::AlphaNumeric(pre_id.to_string()),
+            Identifier::Numeric(1),
+        ];
+        next
+    }
+
+    /// The release this pre-release leads up to.
+    pub fn release(&self) -> Self {
+        let mut next = self.clone();
+        next.clear_suffixes();
+        next
+    }
+}
+
+fn parse_numeric(s: &str, what: &str) -> Result<u64, String> {
+    if s.is_empty() {
+        return Err(format!("{} version is empty", what));
+    }
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("{} '{}' is not a number", what, s));
+    }
+    if s.len() > 1 && s.starts_with('0') {
+        return Err(format!("{} '{}' has a leading zero", what, s));
+    }
+    s.parse()
+        .map_err(|_| format!("{} '{}' is too large", what, s))
+}
+
+fn check_identifier(ident: &str, what: &str) -> Result<(), String> {
+    if ident.is_empty() {
+        return Err(format!("{} contains an empty identifier", what));
+    }
+    if !ident
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    {
+        return Err(format!(
+            "{} identifier '{}' may only contain ASCII alphanumerics and hyphens",
+            what, ident
+        ));
+    }
+    Ok(())
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            let pre: Vec<String> = self.pre.iter().map(|i| i.to_string()).collect();
+            write!(f, "-{}", pre.join("."))?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+/// Precedence as defined by SemVer; build metadata is ignored.
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A Cargo version requirement such as `^1.2`, `>=0.3, <0.5` or `1.*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    pub comparators: Vec<Comparator>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+    Wildcard,
+}
+
+impl Op {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+            Op::Tilde => "~",
+            Op::Caret => "^",
+            Op::Wildcard => "",
+        }
+    }
+}
+
+/// One comparator of a requirement. `minor` and `patch` are `None` when omitted
+/// or written as a wildcard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparator {
+    pub op: Op,
+    /// Whether the operator was written out (`^1.2` rather than `1.2`).
+    pub explicit_op: bool,
+    pub major: u64,
+    pub minor: Option<u64>,
+    pub patch: Option<u64>,
+    pub pre: Vec<Identifier>,
+    /// Build metadata, which Cargo ignores in requirements.
+    pub build: Vec<String>,
+}
+
+impl VersionReq {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err("requirement is empty".to_string());
+        }
+        if s == "*" {
+            return Ok(Self {
+                comparators: Vec::new(),
+            });
+        }
+        let comparators = s
+            .split(',')
+            .map(|c| Comparator::parse(c.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { comparators })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        if !self.comparators.iter().all(|c| c.matches(version)) {
+            return false;
+        }
+        // Pre-releases only match comparators naming the same release.
+        !version.is_prerelease()
+            || self.comparators.iter().any(|c| {
+                !c.pre.is_empty()
+                    && c.major == version.major
+                    && c.minor == Some(version.minor)
+                    && c.patch == Some(version.patch)
+            })
+    }
+}
+
+impl Comparator {
+    fn parse(s: &str) -> Result<Self, String> {
+        if s.is_empty() {
+            return Err("empty comparator".to_string());
+        }
+        let (op, rest) = [
+            (">=", Op::GreaterEq),
+            ("<=", Op::LessEq),
+            (">", Op::Greater),
+            ("<", Op::Less),
+            ("=", Op::Exact),
+            ("~", Op::Tilde),
+            ("^", Op::Caret),
+        ]
+        .iter()
+        .find_map(|(prefix, op)| s.strip_prefix(prefix).map(|rest| (Some(*op), rest)))
+        .unwrap_or((None, s));
+        let rest = rest.trim_start();
+
+        let (rest, build) = match rest.split_once('+') {
+            Some((rest, build)) => (rest, Some(build)),
+            None => (rest, None),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (rest, None),
+        };
+
+        let parts: Vec<&str> = core.split('.').collect();
+        if parts.len() > 3 {
+            return Err(format!("'{}' has more than three components", core));
+        }
+        let is_wildcard = |p: &str| matches!(p, "*" | "x" | "X");
+        if is_wildcard(parts[0]) {
+            return Err("a wildcard must follow a major version".to_string());
+        }
+        let major = parse_numeric(parts[0], "major")?;
+        let mut wildcard = false;
+        let mut numbers = Vec::new();
+        for (part, what) in parts[1..].iter().zip(["minor", "patch"]) {
+            if is_wildcard(part) {
+                wildcard = true;
+            } else if wildcard {
+                return Err("a number cannot follow a wildcard".to_string());
+            } else {
+                numbers.push(parse_numeric(part, what)?);
+            }
+        }
+        if wildcard && op.is_some_and(|op| op != Op::Exact) {
+            return Err("wildcards cannot be combined with an operator".to_string());
+        }
+
+        let mut comparator = Comparator {
+            op: match op {
+                _ if wildcard => Op::Wildcard,
+                Some(op) => op,
+                None => Op::Caret,
+            },
+            explicit_op: op.is_some(),
+            major,
+            minor: numbers.first().copied(),
+            patch: numbers.get(1).copied(),
+            pre: Vec::new(),
+            build: Vec::new(),
+        };
+
+        if let Some(pre) = pre {
+            if comparator.patch.is_none() {
+                return Err("a pre-release requires a full MAJOR.MINOR.PATCH version".to_string());
+            }
+            let version = Version::parse(&format!("0.0.0-{}", pre))?;
+            comparator.pre = version.pre;
+        }
+        if let Some(build) = build {
+            for ident in build.split('.') {
+                check_identifier(ident, "build metadata")?;
+                comparator.build.push(ident.to_string());
+            }
+        }
+
+        Ok(comparator)
+    }
+
+    /// The lowest version the comparator names, with omitted parts as zero.
+    fn lower(&self) -> Version {
+        Version {
+            major: self.major,
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+            pre: self.pre.clone(),
+            build: Vec::new(),
+        }
+    }
+
+    fn matches(&self, v: &Version) -> bool {
+        let lower = self.lower();
+        let same_minor = |minor: u64| v.major == self.major && v.minor == minor;
+        match (self.op, self.minor, self.patch) {
+            (Op::Exact | Op::Wildcard, Some(_), Some(_)) => v.cmp(&lower).is_eq(),
+            (Op::Exact | Op::Wildcard | Op::Tilde, Some(minor), None) => same_minor(minor),
+            (Op::Exact | Op::Wildcard | Op::Tilde, None, _) => v.major == self.major,
+            (Op::Greater, Some(_), Some(_)) => *v > lower,
+            (Op::Greater, Some(minor), None) => (v.major, v.minor) > (self.major, minor),
+            (Op::Greater, None, _) => v.major > self.major,
+            (Op::GreaterEq, _, _) => *v >= lower,
+            (Op::Less, _, _) => *v < lower,
+            (Op::LessEq, Some(_), Some(_)) => *v <= lower,
+            (Op::LessEq, Some(minor), None) => (v.major, v.minor) <= (self.major, minor),
+            (Op::LessEq, None, _) => v.major <= self.major,
+            (Op::Tilde, Some(minor), Some(_)) => same_minor(minor) && *v >= lower,
+            (Op::Caret, None, _) => v.major == self.major,
+            (Op::Caret, Some(minor), patch) => {
+                if self.major > 0 {
+                    v.major == self.major && *v >= lower
+                } else if minor > 0 || patch.is_none() {
+                    same_minor(minor) && *v >= lower
+                } else {
+                    v.cmp(&lower).is_eq()
+                }
+            }
+        }
+    }
+}
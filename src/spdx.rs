@@ -0,0 +1,392 @@
+// src/spdx.rs
+use crate::utils::levenshtein;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+/// SPDX license identifiers, mapped to whether they are deprecated.
+static LICENSES: Lazy<HashMap<&'static str, bool>> =
+    Lazy::new(|| parse_list(include_str!("spdx-licenses.txt")));
+
+/// SPDX license exception identifiers, mapped to whether they are deprecated.
+static EXCEPTIONS: Lazy<HashMap<&'static str, bool>> =
+    Lazy::new(|| parse_list(include_str!("spdx-exceptions.txt")));
+
+fn parse_list(list: &'static str) -> HashMap<&'static str, bool> {
+    list.lines()
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| match l.split_once(' ') {
+            Some((id, flag)) => (id, flag == "deprecated"),
+            None => (l, false),
+        })
+        .collect()
+}
+
+/// Problems found in a license expression.
+#[derive(Default)]
+pub struct Report {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    With,
+    /// The legacy `MIT/Apache-2.0` separator crates.io still accepts.
+    Slash,
+    Id(String),
+}
+
+/// Checks an SPDX license expression (`MIT OR Apache-2.0`,
+/// `GPL-2.0-or-later WITH Classpath-exception-2.0`, `(MIT AND BSD-3-Clause)`).
+pub fn check(expression: &str) -> Report {
+    let mut report = Report::default();
+    let mut tokens = match tokenize(expression) {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            report.errors.push(e);
+            return report;
+        }
+    };
+    for token in &mut tokens {
+        let Token::Id(word) = token else { continue };
+        if let Some(operator) = lowercase_operator(word) {
+            report.errors.push(format!(
+                "License operator '{}' must be uppercase: {}",
+                word,
+                describe(&operator)
+            ));
+            *token = operator;
+        }
+    }
+
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        report: &mut report,
+    };
+    let result = parser.expression().and_then(|_| match parser.peek() {
+        None => Ok(()),
+        Some(token) => Err(format!("unexpected {}", describe(token))),
+    });
+    if let Err(e) = result {
+        report
+            .errors
+            .push(format!("invalid SPDX expression: {}", e));
+    }
+    report
+}
+
+/// Rewrites the mechanical problems in a license expression: lowercase
+/// operators, `/` separators, identifier case, `X+` where `X-or-later` exists and
+/// deprecated identifiers with an `-only` replacement. Returns `None` when there
+/// is nothing to change or the expression does not tokenize.
+pub fn fix(expression: &str) -> Option<String> {
+    let tokens = tokenize(expression).ok()?;
+    let mut fixed = Vec::with_capacity(tokens.len());
+    for token in &tokens {
+        let after_with = fixed.last() == Some(&Token::With);
+        fixed.push(match token {
+            Token::Slash => Token::Or,
+            Token::Id(word) => match lowercase_operator(word) {
+                Some(operator) => operator,
+                None if after_with => Token::Id(
+                    find_case_insensitive(&EXCEPTIONS, word)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| word.clone()),
+                ),
+                None => Token::Id(fix_license(word)),
+            },
+            token => token.clone(),
+        });
+    }
+
+    let fixed = render(&fixed);
+    (fixed != render(&tokens)).then_some(fixed)
+}
+
+fn fix_license(id: &str) -> String {
+    if id.starts_with("LicenseRef-") || id.starts_with("DocumentRef-") {
+        return id.to_string();
+    }
+    let (base, plus) = match id.strip_suffix('+') {
+        Some(base) => (base, true),
+        None => (id, false),
+    };
+    let Some(canonical) = LICENSES
+        .get_key_value(base)
+        .map(|(id, _)| *id)
+        .or_else(|| find_case_insensitive(&LICENSES, base))
+    else {
+        return id.to_string();
+    };
+
+    if plus {
+        let later = format!("{}-or-later", canonical);
+        return if LICENSES.contains_key(later.as_str()) {
+            later
+        } else {
+            format!("{}+", canonical)
+        };
+    }
+    match LICENSES.get(canonical) {
+        Some(true) => replacement(canonical).unwrap_or_else(|| canonical.to_string()),
+        _ => canonical.to_string(),
+    }
+}
+
+fn render(tokens: &[Token]) -> String {
+    let words: Vec<String> = tokens
+        .iter()
+        .map(|token| match token {
+            Token::Open => "(".to_string(),
+            Token::Close => ")".to_string(),
+            Token::And => "AND".to_string(),
+            Token::Or => "OR".to_string(),
+            Token::With => "WITH".to_string(),
+            Token::Slash => "/".to_string(),
+            Token::Id(id) => id.clone(),
+        })
+        .collect();
+    words.join(" ").replace("( ", "(").replace(" )", ")")
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '/' => {
+                chars.next();
+                tokens.push(Token::Slash);
+            }
+            c if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+' | ':') => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+' | ':') {
+                        word.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "WITH" => Token::With,
+                    _ => Token::Id(word),
+                });
+            }
+            c => return Err(format!("invalid character '{}' in license expression", c)),
+        }
+    }
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Open => "'('".to_string(),
+        Token::Close => "')'".to_string(),
+        Token::And => "AND".to_string(),
+        Token::Or => "OR".to_string(),
+        Token::With => "WITH".to_string(),
+        Token::Slash => "'/'".to_string(),
+        Token::Id(id) => format!("'{}'", id),
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    report: &'a mut Report,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// expression := and-expression (OR and-expression)*
+    fn expression(&mut self) -> Result<(), String> {
+        self.and_expression()?;
+        while let Some(token @ (Token::Or | Token::Slash)) = self.peek() {
+            if *token == Token::Slash {
+                self.report
+                    .warnings
+                    .push("'/' as a license separator is deprecated; use OR".to_string());
+            }
+            self.pos += 1;
+            self.and_expression()?;
+        }
+        Ok(())
+    }
+
+    /// and-expression := with-expression (AND with-expression)*
+    fn and_expression(&mut self) -> Result<(), String> {
+        self.with_expression()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            self.with_expression()?;
+        }
+        Ok(())
+    }
+
+    /// with-expression := primary (WITH exception)?
+    fn with_expression(&mut self) -> Result<(), String> {
+        let is_license = self.primary()?;
+        if self.peek() == Some(&Token::With) {
+            self.pos += 1;
+            if !is_license {
+                return Err("WITH must follow a license identifier".to_string());
+            }
+            match self.next() {
+                Some(Token::Id(id)) => self.exception(&id),
+                Some(token) => {
+                    return Err(format!("expected an exception, found {}", describe(&token)))
+                }
+                None => return Err("expected an exception after WITH".to_string()),
+            }
+        }
+        Ok(())
+    }
+
+    /// primary := '(' expression ')' | license. Returns whether it was a license.
+    fn primary(&mut self) -> Result<bool, String> {
+        match self.next() {
+            Some(Token::Open) => {
+                self.expression()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(false),
+                    Some(token) => Err(format!("expected ')', found {}", describe(&token))),
+                    None => Err("unclosed '('".to_string()),
+                }
+            }
+            Some(Token::Id(id)) => {
+                self.license(&id);
+                Ok(true)
+            }
+            Some(token) => Err(format!("expected a license, found {}", describe(&token))),
+            None => Err("expected a license".to_string()),
+        }
+    }
+
+    fn license(&mut self, id: &str) {
+        if id.starts_with("LicenseRef-") || id.starts_with("DocumentRef-") {
+            return;
+        }
+        // `GPL-2.0+` predates the `-or-later` identifiers; `Apache-2.0+` still
+        // means "this version or later" of a listed license.
+        let (base, plus) = match id.strip_suffix('+') {
+            Some(base) => (base, true),
+            None => (id, false),
+        };
+        let later = format!("{}-or-later", base);
+        if plus && LICENSES.contains_key(later.as_str()) {
+            self.report
+                .warnings
+                .push(format!("License '{}' is deprecated; use '{}'", id, later));
+            return;
+        }
+
+        match LICENSES.get(base) {
+            Some(false) => {}
+            Some(true) => self.report.warnings.push(match replacement(base) {
+                Some(new) => format!("License '{}' is deprecated; use '{}'", id, new),
+                None => format!("License '{}' is deprecated", id),
+            }),
+            None => match find_case_insensitive(&LICENSES, base) {
+                Some(canonical) => self.report.warnings.push(format!(
+                    "License '{}' should be written as '{}{}'",
+                    id,
+                    canonical,
+                    if plus { "+" } else { "" }
+                )),
+                None => self.report.errors.push(match suggest(&LICENSES, base) {
+                    Some(s) => format!("Unknown SPDX license '{}'; did you mean '{}'?", id, s),
+                    None => format!("Unknown SPDX license '{}'", id),
+                }),
+            },
+        }
+    }
+
+    fn exception(&mut self, id: &str) {
+        match EXCEPTIONS.get(id) {
+            Some(true) => self
+                .report
+                .warnings
+                .push(format!("License exception '{}' is deprecated", id)),
+            Some(false) => {}
+            None => match find_case_insensitive(&EXCEPTIONS, id) {
+                Some(canonical) => self.report.warnings.push(format!(
+                    "License exception '{}' should be written as '{}'",
+                    id, canonical
+                )),
+                None => self.report.errors.push(match suggest(&EXCEPTIONS, id) {
+                    Some(s) => format!(
+                        "Unknown SPDX license exception '{}'; did you mean '{}'?",
+                        id, s
+                    ),
+                    None => format!("Unknown SPDX license exception '{}'", id),
+                }),
+            },
+        }
+    }
+}
+
+fn lowercase_operator(word: &str) -> Option<Token> {
+    match word.to_ascii_uppercase().as_str() {
+        "AND" => Some(Token::And),
+        "OR" => Some(Token::Or),
+        "WITH" => Some(Token::With),
+        _ => None,
+    }
+}
+
+/// The current identifier for a deprecated one, e.g. `GPL-3.0` -> `GPL-3.0-only`.
+fn replacement(deprecated: &str) -> Option<String> {
+    let only = format!("{}-only", deprecated);
+    LICENSES.contains_key(only.as_str()).then_some(only)
+}
+
+fn find_case_insensitive(list: &HashMap<&'static str, bool>, id: &str) -> Option<&'static str> {
+    list.keys()
+        .find(|candidate| candidate.eq_ignore_ascii_case(id))
+        .copied()
+}
+
+/// The closest non-deprecated identifier, if it is reasonably close.
+fn suggest(list: &HashMap<&'static str, bool>, id: &str) -> Option<&'static str> {
+    let id = id.to_ascii_lowercase();
+    let max = (id.len() / 3).max(2);
+    list.iter()
+        .filter(|(_, &deprecated)| !deprecated)
+        .map(|(candidate, _)| {
+            (
+                levenshtein(&id, &candidate.to_ascii_lowercase()),
+                *candidate,
+            )
+        })
+        .filter(|(distance, _)| *distance <= max)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
@@ -0,0 +1,126 @@
+// src/urls.rs
+
+/// The parts of an absolute URL that the link fields care about.
+pub struct Url<'a> {
+    pub scheme: &'a str,
+    pub userinfo: Option<&'a str>,
+    pub host: &'a str,
+    /// Everything after the host, starting with `/` when present.
+    pub path: &'a str,
+}
+
+impl<'a> Url<'a> {
+    pub fn parse(s: &'a str) -> Result<Self, String> {
+        let (scheme, rest) = s
+            .split_once("://")
+            .ok_or_else(|| "missing scheme (expected https://...)".to_string())?;
+        if scheme.is_empty()
+            || !scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        {
+            return Err(format!("invalid scheme '{}'", scheme));
+        }
+        let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+        let (authority, path) = rest.split_at(end);
+        let (userinfo, host) = match authority.rsplit_once('@') {
+            Some((userinfo, host)) => (Some(userinfo), host),
+            None => (None, authority),
+        };
+        if host.is_empty() {
+            return Err("missing host".to_string());
+        }
+        if s.chars().any(|c| c.is_whitespace()) {
+            return Err("contains whitespace".to_string());
+        }
+        Ok(Self {
+            scheme,
+            userinfo,
+            host,
+            path,
+        })
+    }
+}
+
+/// Checks a `repository`, `homepage` or `documentation` value. Returns the
+/// warnings, or an error when the value is not an acceptable URL.
+pub fn check(field: &str, value: &str) -> Result<Vec<String>, String> {
+    let url = Url::parse(value).map_err(|e| format!("'{}' is not a valid URL: {}", field, e))?;
+    if url.userinfo.is_some() {
+        return Err(format!("'{}' URL must not contain credentials", field));
+    }
+    if !url.scheme.eq_ignore_ascii_case("https") {
+        return Err(format!("'{}' must use https, not {}", field, url.scheme));
+    }
+
+    let mut warnings = Vec::new();
+    if field == "repository" {
+        if url.path.trim_end_matches('/').ends_with(".git") {
+            warnings.push("'repository' should not end with .git".to_string());
+        }
+        if ["/tree/", "/blob/"].iter().any(|p| url.path.contains(p)) {
+            warnings.push(
+                "'repository' should point at the repository root, not a tree/blob path"
+                    .to_string(),
+            );
+        }
+    }
+    Ok(warnings)
+}
+
+/// The canonical `https://host/owner/repo` form of a git remote, e.g. for
+/// `git@github.com:owner/repo.git` or `ssh://git@host/owner/repo`.
+pub fn normalize_repository(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    let (host, path) = if let Some((scheme, _)) = value.split_once("://") {
+        if !matches!(
+            scheme,
+            "https" | "http" | "ssh" | "git" | "git+ssh" | "git+https"
+        ) {
+            return Err(format!("unsupported scheme '{}'", scheme));
+        }
+        let url = Url::parse(value)?;
+        // Drop any port used for ssh access.
+        let host = url.host.split(':').next().unwrap_or(url.host);
+        (host.to_string(), url.path.to_string())
+    } else if let Some((user_host, path)) = value.split_once(':') {
+        // scp-like syntax: [user@]host:path
+        let host = user_host.rsplit('@').next().unwrap_or(user_host);
+        if host.is_empty() || path.is_empty() {
+            return Err(format!("'{}' is not a git remote", value));
+        }
+        (
+            host.to_string(),
+            format!("/{}", path.trim_start_matches('/')),
+        )
+    } else {
+        return Err(format!("'{}' is not a URL or git remote", value));
+    };
+
+    let mut path = path
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('/')
+        .to_string();
+    // GitLab puts its browse paths behind `/-/`.
+    for marker in ["/-/tree/", "/-/blob/", "/tree/", "/blob/"] {
+        if let Some(i) = path.find(marker) {
+            path.truncate(i);
+        }
+    }
+    let path = path.strip_suffix(".git").unwrap_or(&path);
+    if path.is_empty() {
+        return Err(format!("'{}' has no repository path", value));
+    }
+    Ok(format!("https://{}{}", host.to_ascii_lowercase(), path))
+}
+
+/// Compares URLs ignoring case, a trailing slash and a `.git` suffix.
+pub fn same_url(a: &str, b: &str) -> bool {
+    let trim = |url: &str| {
+        let url = url.trim_end_matches('/');
+        url.strip_suffix(".git").unwrap_or(url).to_ascii_lowercase()
+    };
+    trim(a) == trim(b)
+}
@@ -0,0 +1,201 @@
+// src/workspace.rs
+use crate::manifest::Manifest;
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, TableLike};
+
+const DEPENDENCY_SECTIONS: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// A workspace root manifest and the manifests of its members.
+pub struct Workspace {
+    /// The root manifest first, then every member that has its own `Cargo.toml`.
+    pub manifests: Vec<Manifest>,
+}
+
+impl Workspace {
+    /// Finds the workspace `manifest_path` belongs to by walking up to the first
+    /// `Cargo.toml` with a `[workspace]` table. Returns `None` outside a workspace.
+    pub fn discover(manifest_path: &Path) -> Result<Option<Self>> {
+        let start = canonical(manifest_path);
+        let mut root = None;
+        for dir in start.ancestors().skip(1) {
+            let candidate = dir.join("Cargo.toml");
+            if !candidate.is_file() {
+                continue;
+            }
+            let manifest = Manifest::load(&candidate)?;
+            if manifest.doc.contains_key("workspace") {
+                root = Some(manifest);
+                break;
+            }
+        }
+        let Some(root) = root else {
+            return Ok(None);
+        };
+
+        let root_dir = root.path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let workspace = root.doc.get("workspace");
+        let patterns = string_array(workspace.and_then(|w| w.get("members")));
+        let excluded: Vec<PathBuf> = string_array(workspace.and_then(|w| w.get("exclude")))
+            .iter()
+            .map(|p| canonical(&root_dir.join(p)))
+            .collect();
+
+        let mut dirs = Vec::new();
+        for pattern in &patterns {
+            expand_member(&root_dir, pattern, &mut dirs);
+        }
+        dirs.sort();
+        dirs.dedup();
+
+        let root_path = canonical(&root.path);
+        let mut manifests = vec![root];
+        for dir in dirs {
+            let path = dir.join("Cargo.toml");
+            let dir = canonical(&dir);
+            if excluded.iter().any(|e| dir.starts_with(e)) || canonical(&path) == root_path {
+                continue;
+            }
+            if path.is_file() {
+                manifests.push(Manifest::load(&path)?);
+            }
+        }
+
+        let workspace = Self { manifests };
+        if workspace.find(manifest_path).is_none() {
+            return Ok(None);
+        }
+        Ok(Some(workspace))
+    }
+
+    /// Index into `manifests` of the manifest at `path`.
+    pub fn find(&self, path: &Path) -> Option<usize> {
+        let path = canonical(path);
+        self.manifests
+            .iter()
+            .position(|m| canonical(&m.path) == path)
+    }
+}
+
+/// `[workspace.members]` entries may use `*` and `?` wildcards per path segment.
+fn expand_member(root: &Path, pattern: &str, out: &mut Vec<PathBuf>) {
+    let mut current = vec![root.to_path_buf()];
+    for segment in pattern.split('/').filter(|s| !s.is_empty() && *s != ".") {
+        let mut next = Vec::new();
+        for dir in &current {
+            if !segment.contains(['*', '?']) {
+                next.push(dir.join(segment));
+                continue;
+            }
+            let Ok(entries) = fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if entry.path().is_dir() && wildcard_match(segment, &name) {
+                    next.push(entry.path());
+                }
+            }
+        }
+        current = next;
+    }
+    out.extend(current.into_iter().filter(|d| d.is_dir()));
+}
+
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    fn go(p: &[char], n: &[char]) -> bool {
+        match p.first() {
+            None => n.is_empty(),
+            Some('*') => (0..=n.len()).any(|i| go(&p[1..], &n[i..])),
+            Some('?') => !n.is_empty() && go(&p[1..], &n[1..]),
+            Some(c) => n.first() == Some(c) && go(&p[1..], &n[1..]),
+        }
+    }
+    go(&pattern, &name)
+}
+
+fn string_array(item: Option<&toml_edit::Item>) -> Vec<String> {
+    item.and_then(|i| i.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whether `dep` (relative to `base`) points at the package in `crate_dir`.
+pub fn path_points_to(base: &Path, dep: &str, crate_dir: &Path) -> bool {
+    canonical(&base.join(dep)) == canonical(crate_dir)
+}
+
+/// Every dependency table in the document with its key path (e.g.
+/// `["target", "cfg(unix)", "dependencies"]`), as visited by
+/// [`for_each_dependency_table_mut`].
+pub fn dependency_tables(doc: &DocumentMut) -> Vec<(Vec<&str>, &dyn TableLike)> {
+    let root = doc.as_table();
+    let mut tables = Vec::new();
+    for section in DEPENDENCY_SECTIONS {
+        if let Some(table) = root.get(section).and_then(|t| t.as_table_like()) {
+            tables.push((vec![*section], table));
+        }
+    }
+    if let Some(targets) = root.get("target").and_then(|t| t.as_table_like()) {
+        for (name, target) in targets.iter() {
+            for section in DEPENDENCY_SECTIONS {
+                if let Some(table) = target.get(section).and_then(|t| t.as_table_like()) {
+                    tables.push((vec!["target", name, *section], table));
+                }
+            }
+        }
+    }
+    if let Some(table) = root
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(|t| t.as_table_like())
+    {
+        tables.push((vec!["workspace", "dependencies"], table));
+    }
+    tables
+}
+
+/// Calls `f` with the section name (`dependencies`, `dev-dependencies`,
+/// `build-dependencies` or `workspace.dependencies`) and every dependency table in
+/// the document, including `[target.'cfg(..)'.*]` tables.
+pub fn for_each_dependency_table_mut(
+    doc: &mut DocumentMut,
+    mut f: impl FnMut(&str, &mut dyn TableLike),
+) {
+    let root = doc.as_table_mut();
+    for section in DEPENDENCY_SECTIONS {
+        if let Some(table) = root.get_mut(section).and_then(|t| t.as_table_like_mut()) {
+            f(section, table);
+        }
+    }
+    if let Some(targets) = root.get_mut("target").and_then(|t| t.as_table_like_mut()) {
+        for (_, target) in targets.iter_mut() {
+            let Some(target) = target.as_table_like_mut() else {
+                continue;
+            };
+            for section in DEPENDENCY_SECTIONS {
+                if let Some(table) = target.get_mut(section).and_then(|t| t.as_table_like_mut()) {
+                    f(section, table);
+                }
+            }
+        }
+    }
+    if let Some(table) = root
+        .get_mut("workspace")
+        .and_then(|w| w.get_mut("dependencies"))
+        .and_then(|t| t.as_table_like_mut())
+    {
+        f("workspace.dependencies", table);
+    }
+}